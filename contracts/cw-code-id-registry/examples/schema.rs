@@ -3,12 +3,18 @@ use std::fs::create_dir_all;
 
 use cosmwasm_schema::{export_schema, export_schema_with_title, remove_schemas, schema_for};
 
+use cosmwasm_std::Addr;
 use cw_code_id_registry::{
     msg::{
-        ExecuteMsg, GetCodeIdInfoResponse, GetRegistrationResponse, InstantiateMsg,
-        ListRegistrationsResponse, QueryMsg, ReceiveMsg,
+        ExecuteMsg, GetChainResponse, GetDependenciesResponse, GetInterfacesResponse,
+        GetNamespaceResponse, GetRegistrationResponse, GetRegistrationsResponse,
+        GetSchemasResponse, InstantiateByNameResponse, InstantiateMsg, ListByInterfaceResponse,
+        ListChainsResponse, ListDelegationsResponse, ListInstancesResponse,
+        ListQueuedActionsResponse, ListRegistrationProposalsResponse, ListRegistrationsResponse,
+        ListUndeliveredUpdatesResponse, PauseInfoResponse, PredictAddressResponse, QueryMsg,
+        ResolveDependenciesResponse, SignersResponse, SyncChannelsResponse, UpgradePathResponse,
     },
-    state::{Config, PaymentInfo, Registration},
+    state::{ChainInfo, Config, Registration},
 };
 
 fn main() {
@@ -20,14 +26,48 @@ fn main() {
     export_schema(&schema_for!(InstantiateMsg), &out_dir);
     export_schema(&schema_for!(ExecuteMsg), &out_dir);
     export_schema(&schema_for!(QueryMsg), &out_dir);
-    export_schema(&schema_for!(ReceiveMsg), &out_dir);
-    export_schema(&schema_for!(PaymentInfo), &out_dir);
     export_schema(&schema_for!(Registration), &out_dir);
+    export_schema(&schema_for!(ChainInfo), &out_dir);
     export_schema(&schema_for!(GetRegistrationResponse), &out_dir);
-    export_schema(&schema_for!(GetCodeIdInfoResponse), &out_dir);
+    export_schema(&schema_for!(GetRegistrationsResponse), &out_dir);
     export_schema(&schema_for!(ListRegistrationsResponse), &out_dir);
+    export_schema(&schema_for!(PredictAddressResponse), &out_dir);
+    export_schema(&schema_for!(ListInstancesResponse), &out_dir);
+    export_schema(&schema_for!(UpgradePathResponse), &out_dir);
+    export_schema(&schema_for!(GetDependenciesResponse), &out_dir);
+    export_schema(&schema_for!(ResolveDependenciesResponse), &out_dir);
+    export_schema(&schema_for!(GetInterfacesResponse), &out_dir);
+    export_schema(&schema_for!(ListByInterfaceResponse), &out_dir);
+    export_schema(&schema_for!(GetSchemasResponse), &out_dir);
+    export_schema(&schema_for!(SyncChannelsResponse), &out_dir);
+    export_schema(&schema_for!(ListUndeliveredUpdatesResponse), &out_dir);
+    export_schema(&schema_for!(SignersResponse), &out_dir);
+    export_schema(&schema_for!(PauseInfoResponse), &out_dir);
+    export_schema(&schema_for!(ListDelegationsResponse), &out_dir);
+    export_schema(&schema_for!(ListRegistrationProposalsResponse), &out_dir);
+    export_schema(&schema_for!(ListQueuedActionsResponse), &out_dir);
+    export_schema(&schema_for!(GetChainResponse), &out_dir);
+    export_schema(&schema_for!(ListChainsResponse), &out_dir);
+    export_schema(&schema_for!(GetNamespaceResponse), &out_dir);
+    export_schema(&schema_for!(InstantiateByNameResponse), &out_dir);
 
     // Auto TS code generation expects the query return type as QueryNameResponse
     // Here we map query responses to the correct name
+    export_schema_with_title(&schema_for!(Addr), &out_dir, "AdminResponse");
     export_schema_with_title(&schema_for!(Config), &out_dir, "ConfigResponse");
+    export_schema_with_title(
+        &schema_for!(GetRegistrationResponse),
+        &out_dir,
+        "GetCodeIdInfoResponse",
+    );
+    export_schema_with_title(
+        &schema_for!(GetRegistrationsResponse),
+        &out_dir,
+        "GetCodeIdInfosResponse",
+    );
+    export_schema_with_title(
+        &schema_for!(GetRegistrationResponse),
+        &out_dir,
+        "GetInstanceRegistrationResponse",
+    );
 }
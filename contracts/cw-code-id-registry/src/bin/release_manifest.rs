@@ -0,0 +1,55 @@
+//! Builds a RegisterBatch message registering a release's artifacts.
+//!
+//! Usage: release_manifest --chain-id <chain_id> --version <version>
+//!            <checksums.txt> <store-code-log>
+//!
+//! Requires the manifest feature, e.g.
+//! `cargo run --features manifest --bin release_manifest -- ...`.
+//!
+//! checksums.txt is the one cosmwasm/workspace-optimizer writes to
+//! artifacts/. The store-code log has a `<file> <code_id>` line for each
+//! artifact stored on chain_id. The message is printed as JSON, ready to
+//! pass to `wasmd tx wasm execute`.
+
+use std::{env, fs, process};
+
+use cosmwasm_std::to_json_string;
+use cw_code_id_registry::manifest::register_batch;
+
+const USAGE: &str = "Usage: release_manifest --chain-id <chain_id> --version <version> <checksums.txt> <store-code-log>";
+
+fn main() {
+    if let Err(error) = run(env::args().skip(1).collect()) {
+        eprintln!("{}", error);
+        process::exit(1);
+    }
+}
+
+fn run(args: Vec<String>) -> Result<(), String> {
+    let mut chain_id = None;
+    let mut version = None;
+    let mut files = vec![];
+
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--chain-id" => chain_id = args.next(),
+            "--version" => version = args.next(),
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return Ok(());
+            }
+            _ => files.push(arg),
+        }
+    }
+    let (chain_id, version, checksums, log) = match (chain_id, version, &files[..]) {
+        (Some(chain_id), Some(version), [checksums, log]) => (chain_id, version, checksums, log),
+        _ => return Err(USAGE.to_string()),
+    };
+
+    let read = |path: &str| fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e));
+    let msg = register_batch(&read(checksums)?, &read(log)?, &chain_id, &version)
+        .map_err(|e| e.to_string())?;
+    println!("{}", to_json_string(&msg).map_err(|e| e.to_string())?);
+    Ok(())
+}
@@ -1,24 +1,55 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    coins, to_binary, Addr, BankMsg, Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Order,
-    Response, StdError, StdResult, Uint128, WasmMsg,
+    attr, from_json, instantiate2_address, to_json_binary, Addr, Attribute, BankMsg, Binary, Coin,
+    CosmosMsg, Deps, DepsMut, Empty, Env, Event, IbcMsg, MessageInfo, Order, Reply, Response,
+    StdError, StdResult, SubMsg, Uint128, Uint64, WasmMsg,
 };
 use cw2::set_contract_version;
-use cw20::TokenInfoResponse;
-use cw_utils::must_pay;
+use cw4::Cw4Contract;
+use cw_storage_plus::Bound;
+use cw_utils::{nonpayable, parse_reply_instantiate_data, Expiration, NativeBalance};
+use semver::{Version, VersionReq};
+use sha2::{Digest, Sha256};
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, VecDeque};
 
 use crate::error::ContractError;
+use crate::events::{register_event, unregister_event, update_admin_event};
+use crate::ibc::{remove_sync_channel, sync_msg, sync_msgs};
 use crate::msg::{
-    ExecuteMsg, GetRegistrationResponse, InstantiateMsg, ListRegistrationsResponse, QueryMsg,
+    ExecuteMsg, GetChainResponse, GetDependenciesResponse, GetInterfacesResponse,
+    GetNamespaceResponse, GetRegistrationResponse, GetRegistrationsResponse, GetSchemasResponse,
+    InstantiateByNameResponse, InstantiateMsg, InterfaceImplementation, ListByInterfaceResponse,
+    ListChainsResponse, ListDelegationsResponse, ListInstancesResponse, ListQueuedActionsResponse,
+    ListRegistrationProposalsResponse, ListRegistrationsResponse, ListUndeliveredUpdatesResponse,
+    PauseInfoResponse, PayloadSignature, PredictAddressResponse, QueryMsg, RateLimitUpdate,
+    RegisterMsg, RegistrationRequest, RegistryPacket, ResolveDependenciesResponse, SignedPayload,
+    SignersResponse, SyncChannelsResponse, SyncPacket, UncheckedGuardian, UnregisterMsg,
+    UpgradePathResponse, UpgradeStep,
 };
 use crate::state::{
-    Registration, ADMIN, CHAIN_ID_CODE_ID_TO_REGISTRATION, NAME_CHAIN_ID_VERSION_TO_REGISTRATION,
+    ChainInfo, Config, Delegation, Dependency, Guardian, InstanceInfo, Interface, KeyType,
+    MessageSchemas, MigrationEdge, NamespaceInfo, PendingInstantiation, PublisherUsage,
+    QueuedAction, Registration, RegistrationMode, RegistrationProposal, Signer, SignerSet,
+    SyncPeer, TimelockedAction, ValidationRules, CHAINS, CHAIN_ID_CODE_ID_INSTANCES,
+    CHAIN_ID_CODE_ID_TO_REGISTRATION, CONFIG, DELEGATIONS, DEPENDENCIES, IMPORT_NONCE, INSTANCES,
+    INTERFACES, INTERFACE_IMPLEMENTATIONS, MIGRATION_EDGES, NAMESPACES,
+    NAME_CHAIN_ID_VERSION_TO_REGISTRATION, NAME_PUBLISHERS, NEXT_ACTION_ID, NEXT_PROPOSAL_ID,
+    NEXT_REPLY_ID, PENDING_INSTANTIATIONS, PUBLISHER_USAGE, QUEUED_ACTIONS, REGISTRATION_PROPOSALS,
+    SCHEMAS, SIGNER_SET, SYNC_CHANNELS, SYNC_PEERS, TRUSTED_CHANNEL, UNDELIVERED_UPDATES,
+};
+use crate::validation::{
+    namespace_of, validate_contract_name, validate_namespace, validate_registration,
+    validate_schema_hash, validate_version, validate_version_req, MAX_NAME_SEGMENT_LENGTH,
 };
 
 const CONTRACT_NAME: &str = "crates.io:cw-code-id-registry";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut,
@@ -29,7 +60,20 @@ pub fn instantiate(
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
     let validated_admin = deps.api.addr_validate(&msg.admin)?;
-    ADMIN.save(deps.storage, &validated_admin)?;
+    CONFIG.save(
+        deps.storage,
+        &Config {
+            admin: validated_admin,
+            registration_mode: RegistrationMode::AdminOnly,
+            validation: ValidationRules::default(),
+            registration_fee: vec![],
+            paused_until: None,
+            frozen: false,
+            timelock_delay: 0,
+            guardian: None,
+            rate_limit: None,
+        },
+    )?;
     Ok(Response::new().add_attribute("action", "instantiate"))
 }
 
@@ -40,6 +84,18 @@ pub fn execute(
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
+    // The admin may still recover a paused registry and refund proposals.
+    match msg {
+        ExecuteMsg::Pause { .. }
+        | ExecuteMsg::Unpause {}
+        | ExecuteMsg::Freeze {}
+        | ExecuteMsg::UpdateAdmin { .. }
+        | ExecuteMsg::ExecuteQueued { .. }
+        | ExecuteMsg::CancelQueued { .. }
+        | ExecuteMsg::RejectRegistration { .. } => assert_not_frozen(deps.as_ref())?,
+        _ => assert_writable(deps.as_ref(), &env)?,
+    }
+
     match msg {
         ExecuteMsg::Register {
             contract_name: name,
@@ -47,20 +103,245 @@ pub fn execute(
             chain_id,
             code_id,
             checksum,
-        } => execute_register(deps, info, name, version, chain_id, code_id, checksum),
+        } => execute_register(deps, env, info, name, version, chain_id, code_id, checksum),
         ExecuteMsg::Unregister {
             contract_name,
             chain_id,
             code_id,
             version,
-        } => execute_unregister(deps, info.sender, contract_name, chain_id, code_id, version),
+        } => execute_unregister(
+            deps,
+            env,
+            info.sender,
+            contract_name,
+            chain_id,
+            code_id,
+            version,
+        ),
+        ExecuteMsg::RegisterBatch { registrations } => {
+            execute_register_batch(deps, env, info, registrations)
+        }
+        ExecuteMsg::UnregisterBatch { registrations } => {
+            execute_unregister_batch(deps, env, info, registrations)
+        }
+        ExecuteMsg::InstantiateByName {
+            name,
+            version_req,
+            msg,
+            label,
+            admin,
+            funds,
+        } => execute_instantiate_by_name(
+            deps,
+            env,
+            info,
+            name,
+            version_req,
+            msg,
+            label,
+            admin,
+            funds,
+        ),
+        ExecuteMsg::Instantiate2ByName {
+            name,
+            version,
+            msg,
+            label,
+            admin,
+            funds,
+            salt,
+        } => execute_instantiate2_by_name(
+            deps, env, info, name, version, msg, label, admin, funds, salt,
+        ),
+        ExecuteMsg::ReportInstance { address } => execute_report_instance(deps, env, address),
+        ExecuteMsg::AddMigrationEdge {
+            contract_name,
+            from_version,
+            to_version,
+            migrate_msg,
+            breaking,
+        } => execute_add_migration_edge(
+            deps,
+            info,
+            contract_name,
+            from_version,
+            to_version,
+            migrate_msg,
+            breaking,
+        ),
+        ExecuteMsg::RemoveMigrationEdge {
+            contract_name,
+            from_version,
+            to_version,
+        } => execute_remove_migration_edge(deps, info, contract_name, from_version, to_version),
+        ExecuteMsg::MigrateToVersion {
+            contract_addr,
+            name,
+            version,
+            msg,
+        } => execute_migrate_to_version(deps, env, info, contract_addr, name, version, msg),
+        ExecuteMsg::SetDependencies {
+            contract_name,
+            version,
+            dependencies,
+        } => execute_set_dependencies(deps, info, contract_name, version, dependencies),
+        ExecuteMsg::SetInterfaces {
+            contract_name,
+            version,
+            interfaces,
+        } => execute_set_interfaces(deps, info, contract_name, version, interfaces),
+        ExecuteMsg::SetSchemas {
+            contract_name,
+            version,
+            schemas,
+        } => execute_set_schemas(deps, info, contract_name, version, schemas),
+        ExecuteMsg::SetTrustedChannel { channel_id } => {
+            execute_set_trusted_channel(deps, info, channel_id)
+        }
+        ExecuteMsg::AddSyncPeer {
+            connection_id,
+            port_id,
+        } => execute_add_sync_peer(deps, info, connection_id, port_id),
+        ExecuteMsg::RemoveSyncPeer {
+            connection_id,
+            port_id,
+        } => execute_remove_sync_peer(deps, info, connection_id, port_id),
+        ExecuteMsg::CloseSyncChannel { channel_id } => {
+            execute_close_sync_channel(deps, info, channel_id)
+        }
+        ExecuteMsg::Resync { channel_id } => execute_resync(deps, env, info, channel_id),
+        ExecuteMsg::ImportSigned {
+            payload,
+            signatures,
+        } => execute_import_signed(deps, env, info, payload, signatures),
+        ExecuteMsg::SetSigners { signers, threshold } => {
+            execute_set_signers(deps, env, info, signers, threshold)
+        }
+        ExecuteMsg::Pause { until } => execute_pause(deps, env, info, until),
+        ExecuteMsg::Unpause {} => execute_unpause(deps, info),
+        ExecuteMsg::Freeze {} => execute_freeze(deps, info),
+        ExecuteMsg::SetTimelockDelay { blocks } => {
+            execute_set_timelock_delay(deps, env, info, blocks)
+        }
+        ExecuteMsg::ExecuteQueued { id } => execute_execute_queued(deps, env, id),
+        ExecuteMsg::CancelQueued { id } => execute_cancel_queued(deps, info, id),
+        ExecuteMsg::SetGuardian { guardian } => execute_set_guardian(deps, env, info, guardian),
+        ExecuteMsg::Delegate {
+            delegate,
+            names,
+            max_registrations,
+            period,
+            expiration,
+        } => execute_delegate(
+            deps,
+            env,
+            info,
+            delegate,
+            names,
+            max_registrations,
+            period,
+            expiration,
+        ),
+        ExecuteMsg::RevokeDelegation { delegate } => {
+            execute_revoke_delegation(deps, info, delegate)
+        }
+        ExecuteMsg::UpdateConfig {
+            registration_mode,
+            validation,
+            registration_fee,
+            rate_limit,
+        } => execute_update_config(
+            deps,
+            info,
+            registration_mode,
+            validation,
+            registration_fee,
+            rate_limit,
+        ),
+        ExecuteMsg::ApproveRegistration { id } => execute_approve_registration(deps, env, info, id),
+        ExecuteMsg::RejectRegistration { id } => execute_reject_registration(deps, info, id),
         ExecuteMsg::UpdateAdmin { admin } => execute_update_admin(deps, env, info, admin),
+        ExecuteMsg::AddChain {
+            chain_id,
+            bech32_prefix,
+            pretty_name,
+            is_testnet,
+        } => execute_add_chain(deps, info, chain_id, bech32_prefix, pretty_name, is_testnet),
+        ExecuteMsg::RegisterNamespace { namespace, owner } => {
+            execute_register_namespace(deps, info, namespace, owner)
+        }
+        ExecuteMsg::UpdateNamespaceOwner { namespace, owner } => {
+            execute_update_namespace_owner(deps, info, namespace, owner)
+        }
+        ExecuteMsg::AddNamespaceDelegate {
+            namespace,
+            delegate,
+        } => execute_add_namespace_delegate(deps, info, namespace, delegate),
+        ExecuteMsg::RemoveNamespaceDelegate {
+            namespace,
+            delegate,
+        } => execute_remove_namespace_delegate(deps, info, namespace, delegate),
+    }
+}
+
+/// Names of the form `namespace/name` may only be published by the
+/// namespace owner and its delegates. All other names may only be published
+/// by the admin and, in open registration mode, by whoever first registered
+/// them.
+fn assert_can_publish(deps: Deps, sender: &Addr, contract_name: &str) -> Result<(), ContractError> {
+    match namespace_of(contract_name) {
+        Some(namespace) => {
+            let namespace = NAMESPACES
+                .may_load(deps.storage, namespace)?
+                .ok_or_else(|| ContractError::UnknownNamespace(namespace.to_string()))?;
+            if *sender != namespace.owner && !namespace.delegates.contains(sender) {
+                return Err(ContractError::UnauthorizedRegistration {});
+            }
+        }
+        None => {
+            let config = CONFIG.load(deps.storage)?;
+            let is_publisher = config.registration_mode == RegistrationMode::Open
+                && NAME_PUBLISHERS
+                    .may_load(deps.storage, contract_name)?
+                    .as_ref()
+                    == Some(sender);
+            if *sender != config.admin && !is_publisher {
+                return Err(ContractError::UnauthorizedRegistration {});
+            }
+        }
+    }
+    Ok(())
+}
+
+fn assert_not_frozen(deps: Deps) -> Result<(), ContractError> {
+    if CONFIG.load(deps.storage)?.frozen {
+        return Err(ContractError::Frozen {});
+    }
+    Ok(())
+}
+
+/// Writes are rejected while the registry is paused or frozen.
+pub(crate) fn assert_writable(deps: Deps, env: &Env) -> Result<(), ContractError> {
+    assert_not_frozen(deps)?;
+    match CONFIG.load(deps.storage)?.paused_until {
+        Some(until) if !until.is_expired(&env.block) => Err(ContractError::Paused(until)),
+        _ => Ok(()),
+    }
+}
+
+/// Followers only accept registration updates from their trusted channel.
+fn assert_not_follower(deps: Deps) -> Result<(), ContractError> {
+    match TRUSTED_CHANNEL.may_load(deps.storage)? {
+        Some(channel_id) => Err(ContractError::FollowerRegistry(channel_id)),
+        None => Ok(()),
     }
 }
 
 /// (name, version, chain-id) --> code-id registrations may also be updated using this routine.
+#[allow(clippy::too_many_arguments)]
 pub fn execute_register(
-    deps: DepsMut,
+    mut deps: DepsMut,
+    env: Env,
     info: MessageInfo,
     contract_name: String,
     version: String,
@@ -68,27 +349,358 @@ pub fn execute_register(
     code_id: u64,
     checksum: String,
 ) -> Result<Response, ContractError> {
-    let admin = ADMIN.load(deps.storage)?;
-    if info.sender != admin {
-        return Err(ContractError::UnauthorizedRegistration {});
+    let msg = RegisterMsg {
+        contract_name,
+        version,
+        chain_id,
+        code_id,
+        checksum,
+    };
+    let fee = charge_registration_fee(deps.as_ref(), &info, 1)?;
+    let (attributes, events, msgs) = register(deps.branch(), &env, &info.sender, msg, &fee)?;
+
+    Ok(Response::new()
+        .add_messages(msgs)
+        .add_attribute("action", "register_code_id")
+        .add_attributes(attributes)
+        .add_events(events))
+}
+
+pub fn execute_unregister(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    contract_name: String,
+    chain_id: String,
+    code_id: u64,
+    version: String,
+) -> Result<Response, ContractError> {
+    let (attributes, events, sync) = unregister(
+        deps,
+        &env,
+        &sender,
+        UnregisterMsg {
+            contract_name,
+            chain_id,
+            code_id,
+            version,
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_messages(sync)
+        .add_attribute("action", "unregister")
+        .add_attributes(attributes)
+        .add_events(events))
+}
+
+/// Registrations are applied in order and all fail if any one does.
+pub fn execute_register_batch(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    registrations: Vec<RegisterMsg>,
+) -> Result<Response, ContractError> {
+    let fee = charge_registration_fee(deps.as_ref(), &info, registrations.len())?;
+    let mut response = Response::new()
+        .add_attribute("action", "register_batch")
+        .add_attribute("count", registrations.len().to_string());
+    for (index, msg) in registrations.into_iter().enumerate() {
+        let (attributes, events, msgs) = register(deps.branch(), &env, &info.sender, msg, &fee)
+            .map_err(|error| ContractError::BatchEntry {
+                index,
+                error: Box::new(error),
+            })?;
+        response = response
+            .add_messages(msgs)
+            .add_attribute("index", index.to_string())
+            .add_attributes(attributes)
+            .add_events(events);
+    }
+    Ok(response)
+}
+
+/// Unregistrations are applied in order and all fail if any one does.
+pub fn execute_unregister_batch(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    registrations: Vec<UnregisterMsg>,
+) -> Result<Response, ContractError> {
+    let mut response = Response::new()
+        .add_attribute("action", "unregister_batch")
+        .add_attribute("count", registrations.len().to_string());
+    for (index, msg) in registrations.into_iter().enumerate() {
+        let (attributes, events, sync) = unregister(deps.branch(), &env, &info.sender, msg)
+            .map_err(|error| ContractError::BatchEntry {
+                index,
+                error: Box::new(error),
+            })?;
+        response = response
+            .add_messages(sync)
+            .add_attribute("index", index.to_string())
+            .add_attributes(attributes)
+            .add_events(events);
+    }
+    Ok(response)
+}
+
+/// Counts a registration of contract_name against sender's delegation,
+/// returning false if sender has no delegation covering it.
+fn use_delegation(
+    deps: DepsMut,
+    env: &Env,
+    sender: &Addr,
+    contract_name: &str,
+) -> Result<bool, ContractError> {
+    let mut delegation = match DELEGATIONS.may_load(deps.storage, sender)? {
+        Some(delegation)
+            if !delegation.expiration.is_expired(&env.block)
+                && delegation.names.iter().any(|name| name == contract_name) =>
+        {
+            delegation
+        }
+        _ => return Ok(false),
+    };
+
+    if env.block.height >= delegation.period_start.saturating_add(delegation.period) {
+        delegation.period_start = env.block.height;
+        delegation.used = 0;
+    }
+    if delegation.used >= delegation.max_registrations {
+        return Err(ContractError::DelegationExhausted(
+            delegation.period_start.saturating_add(delegation.period),
+        ));
+    }
+    delegation.used += 1;
+    DELEGATIONS.save(deps.storage, sender, &delegation)?;
+
+    Ok(true)
+}
+
+/// Counts a registration of contract_name against sender's rate limit.
+fn use_rate_limit(
+    deps: DepsMut,
+    env: &Env,
+    sender: &Addr,
+    contract_name: &str,
+) -> Result<(), ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let rate_limit = match config.rate_limit {
+        Some(rate_limit) => rate_limit,
+        None => return Ok(()),
+    };
+    if *sender == config.admin {
+        return Ok(());
+    }
+
+    let mut usage = PUBLISHER_USAGE
+        .may_load(deps.storage, sender)?
+        .unwrap_or_default();
+    if env.block.height >= usage.period_start.saturating_add(rate_limit.period) {
+        usage = PublisherUsage {
+            period_start: env.block.height,
+            ..Default::default()
+        };
+    }
+    let retry_at = usage.period_start.saturating_add(rate_limit.period);
+
+    if rate_limit
+        .max_registrations
+        .is_some_and(|max| usage.registrations >= max)
+    {
+        return Err(ContractError::RateLimited { retry_at });
+    }
+    usage.registrations += 1;
+
+    let is_new_name = NAME_CHAIN_ID_VERSION_TO_REGISTRATION
+        .sub_prefix(contract_name)
+        .keys_raw(deps.storage, None, None, Order::Ascending)
+        .next()
+        .is_none();
+    if is_new_name {
+        if rate_limit
+            .max_new_names
+            .is_some_and(|max| usage.new_names >= max)
+        {
+            return Err(ContractError::RateLimited { retry_at });
+        }
+        usage.new_names += 1;
+    }
+
+    PUBLISHER_USAGE.save(deps.storage, sender, &usage)?;
+    Ok(())
+}
+
+/// Attributes and events describing a registry update, and messages syncing
+/// it to follower registries.
+type Update = (Vec<Attribute>, Vec<Event>, Vec<CosmosMsg>);
+
+/// Non-admin registrations must pay the registration fee for each of count
+/// registrations, returning the fee due per registration. Registrations
+/// with no fee due may not send funds.
+fn charge_registration_fee(
+    deps: Deps,
+    info: &MessageInfo,
+    count: usize,
+) -> Result<Vec<Coin>, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.registration_fee.is_empty() || info.sender == config.admin {
+        nonpayable(info)?;
+        return Ok(vec![]);
+    }
+
+    let total: Vec<Coin> = config
+        .registration_fee
+        .iter()
+        .map(|coin| Coin {
+            amount: coin.amount * Uint128::from(count as u128),
+            denom: coin.denom.clone(),
+        })
+        .collect();
+    assert_funds_match(info.funds.clone(), &total)?;
+
+    Ok(config.registration_fee)
+}
+
+/// Sends a registration fee to recipient, if there is one.
+fn pay_fee(recipient: &Addr, fee: Vec<Coin>) -> Option<BankMsg> {
+    if fee.is_empty() {
+        return None;
+    }
+    Some(BankMsg::Send {
+        to_address: recipient.to_string(),
+        amount: fee,
+    })
+}
+
+/// In open registration mode, gives sender a name nobody has registered,
+/// returning false if it has been registered.
+fn claim_name(deps: DepsMut, sender: &Addr, contract_name: &str) -> StdResult<bool> {
+    let registered = NAME_PUBLISHERS.has(deps.storage, contract_name)
+        || NAME_CHAIN_ID_VERSION_TO_REGISTRATION
+            .sub_prefix(contract_name)
+            .keys_raw(deps.storage, None, None, Order::Ascending)
+            .next()
+            .is_some();
+    if registered {
+        return Ok(false);
+    }
+    NAME_PUBLISHERS.save(deps.storage, contract_name, sender)?;
+    Ok(true)
+}
+
+/// Registers a single code ID, or proposes it in proposal registration
+/// mode, returning attributes and events describing it and messages syncing
+/// it to follower registries and forwarding its fee to the admin. Proposals
+/// hold their fee until the admin approves or rejects them.
+fn register(
+    mut deps: DepsMut,
+    env: &Env,
+    sender: &Addr,
+    msg: RegisterMsg,
+    fee: &[Coin],
+) -> Result<Update, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    validate_contract_name(&msg.contract_name)?;
+    let version = validate_version(&msg.version)?;
+    validate_registration(&config.validation, &msg, &version)?;
+
+    assert_not_follower(deps.as_ref())?;
+    let mut propose = false;
+    if let Err(error) = assert_can_publish(deps.as_ref(), sender, &msg.contract_name) {
+        if !use_delegation(deps.branch(), env, sender, &msg.contract_name)? {
+            match config.registration_mode {
+                RegistrationMode::Open
+                    if namespace_of(&msg.contract_name).is_none()
+                        && claim_name(deps.branch(), sender, &msg.contract_name)? => {}
+                RegistrationMode::Proposal if namespace_of(&msg.contract_name).is_none() => {
+                    propose = true
+                }
+                _ => return Err(error),
+            }
+        }
+    }
+
+    // Can only register on chains in the catalog.
+    if !CHAINS.has(deps.storage, &msg.chain_id) {
+        return Err(ContractError::UnknownChain(msg.chain_id));
+    }
+
+    use_rate_limit(deps.branch(), env, sender, &msg.contract_name)?;
+
+    if propose {
+        return Ok((
+            propose_registration(deps, sender, msg, fee)?,
+            vec![],
+            vec![],
+        ));
     }
+    let mut msgs: Vec<CosmosMsg> =
+        sync_msgs(deps.branch(), env, &RegistryPacket::Register(msg.clone()))?
+            .into_iter()
+            .map(Into::into)
+            .collect();
+    msgs.extend(pay_fee(&config.admin, fee.to_vec()).map(Into::into));
+    let (attributes, event) = store_registration(deps, msg)?;
+    Ok((attributes, vec![event], msgs))
+}
+
+fn propose_registration(
+    deps: DepsMut,
+    sender: &Addr,
+    msg: RegisterMsg,
+    fee: &[Coin],
+) -> Result<Vec<Attribute>, ContractError> {
+    let id = NEXT_PROPOSAL_ID.may_load(deps.storage)?.unwrap_or_default();
+    NEXT_PROPOSAL_ID.save(deps.storage, &(id + 1))?;
+
+    let attributes = vec![
+        attr("proposal_id", id.to_string()),
+        attr("contract_name", msg.contract_name.clone()),
+    ];
+    REGISTRATION_PROPOSALS.save(
+        deps.storage,
+        id,
+        &RegistrationProposal {
+            id,
+            proposer: sender.clone(),
+            registration: msg,
+            fee: fee.to_vec(),
+        },
+    )?;
+
+    Ok(attributes)
+}
+
+/// Saves a registration without checking who may publish it. Registrations
+/// received from the trusted channel were already checked by the canonical
+/// registry.
+fn store_registration(
+    deps: DepsMut,
+    msg: RegisterMsg,
+) -> Result<(Vec<Attribute>, Event), ContractError> {
+    let RegisterMsg {
+        contract_name,
+        version,
+        chain_id,
+        code_id,
+        checksum,
+    } = msg;
 
     // Can't re-register a code ID on a chain.
     if CHAIN_ID_CODE_ID_TO_REGISTRATION
         .may_load(deps.storage, (&chain_id, code_id))?
         .is_some()
     {
-        return Err(ContractError::CodeIDAlreadyRegistered(
-            code_id,
-            chain_id.clone(),
-        ));
+        return Err(ContractError::CodeIDAlreadyRegistered(code_id, chain_id));
     }
 
     let registration = Registration {
         contract_name: contract_name.clone(),
         version: version.clone(),
         code_id,
-        checksum: checksum.clone(),
+        checksum,
     };
 
     // Add to state.
@@ -99,134 +711,2174 @@ pub fn execute_register(
         &registration,
     )?;
 
-    Ok(Response::new()
-        .add_attribute("action", "register_code_id")
-        .add_attribute("code_id", code_id.to_string())
-        .add_attribute("contract_name", contract_name))
+    Ok((
+        vec![
+            attr("code_id", code_id.to_string()),
+            attr("contract_name", contract_name),
+        ],
+        register_event(&chain_id, &registration),
+    ))
 }
 
-pub fn execute_unregister(
-    deps: DepsMut,
-    sender: Addr,
-    contract_name: String,
-    chain_id: String,
-    code_id: u64,
-    version: String,
-) -> Result<Response, ContractError> {
-    let admin = ADMIN.load(deps.storage)?;
+/// Unregisters a single code ID, or queues its unregistration if there is a
+/// timelock, returning attributes and events describing it and messages
+/// syncing it to follower registries.
+fn unregister(
+    mut deps: DepsMut,
+    env: &Env,
+    sender: &Addr,
+    msg: UnregisterMsg,
+) -> Result<Update, ContractError> {
+    assert_can_unregister(deps.as_ref(), sender, &msg)?;
+
+    if let Some(delay) = timelock_delay(deps.as_ref())? {
+        let attributes = queue_action(
+            deps,
+            env,
+            sender.clone(),
+            delay,
+            TimelockedAction::Unregister(msg),
+        )?;
+        return Ok((attributes, vec![], vec![]));
+    }
+
+    let sync = sync_msgs(deps.branch(), env, &RegistryPacket::Unregister(msg.clone()))?;
+    let (attributes, event) = remove_registration(deps, msg)?;
+    Ok((
+        attributes,
+        vec![event],
+        sync.into_iter().map(Into::into).collect(),
+    ))
+}
+
+/// Checks sender may unregister an existing registration.
+fn assert_can_unregister(
+    deps: Deps,
+    sender: &Addr,
+    msg: &UnregisterMsg,
+) -> Result<(), ContractError> {
+    assert_not_follower(deps)?;
+    // Only allow those who may publish the name to unregister.
+    assert_can_publish(deps, sender, &msg.contract_name)?;
+    assert_registered(deps, msg)
+}
 
-    // Only allow admin to unregister.
-    if sender != admin {
-        return Err(ContractError::UnauthorizedRegistration {});
+/// Checks the version and code ID of msg refer to the same registration.
+fn assert_registered(deps: Deps, msg: &UnregisterMsg) -> Result<(), ContractError> {
+    match NAME_CHAIN_ID_VERSION_TO_REGISTRATION.may_load(
+        deps.storage,
+        (&msg.contract_name, &msg.chain_id, &msg.version),
+    )? {
+        Some(registration) if registration.code_id == msg.code_id => Ok(()),
+        _ => Err(ContractError::NotFound {}),
     }
+}
+
+/// Removes a registration without checking who may publish it.
+fn remove_registration(
+    deps: DepsMut,
+    msg: UnregisterMsg,
+) -> Result<(Vec<Attribute>, Event), ContractError> {
+    let UnregisterMsg {
+        contract_name,
+        chain_id,
+        code_id,
+        version,
+    } = msg;
+
+    // The version and code ID must refer to the same registration.
+    let registration = match NAME_CHAIN_ID_VERSION_TO_REGISTRATION
+        .may_load(deps.storage, (&contract_name, &chain_id, &version))?
+    {
+        Some(registration) if registration.code_id == code_id => registration,
+        _ => return Err(ContractError::NotFound {}),
+    };
 
     // Remove registration.
     NAME_CHAIN_ID_VERSION_TO_REGISTRATION
         .remove(deps.storage, (&contract_name, &chain_id, &version));
     CHAIN_ID_CODE_ID_TO_REGISTRATION.remove(deps.storage, (&chain_id, code_id));
 
-    Ok(Response::new()
-        .add_attribute("action", "unregister")
-        .add_attribute("chain_id", chain_id)
-        .add_attribute("contract_name", contract_name)
-        .add_attribute("code_id", code_id.to_string()))
+    let event = unregister_event(&chain_id, &registration);
+    Ok((
+        vec![
+            attr("chain_id", chain_id),
+            attr("contract_name", contract_name),
+            attr("code_id", code_id.to_string()),
+        ],
+        event,
+    ))
 }
 
-pub fn execute_update_admin(
+/// Applies a registry update received from a trusted source.
+pub(crate) fn apply_update(
     deps: DepsMut,
-    _env: Env,
-    info: MessageInfo,
-    new_admin: String,
-) -> Result<Response, ContractError> {
-    let current_admin = ADMIN.load(deps.storage)?;
-    if info.sender != current_admin {
-        return Err(ContractError::UnauthorizedUpdateAdmin {});
+    update: RegistryPacket,
+) -> Result<(Vec<Attribute>, Event), ContractError> {
+    match update {
+        RegistryPacket::Register(msg) => store_registration(deps, msg),
+        RegistryPacket::Unregister(msg) => remove_registration(deps, msg),
     }
+}
 
-    let validated_admin = deps.api.addr_validate(&new_admin)?;
-    ADMIN.save(deps.storage, &validated_admin)?;
-
-    Ok(Response::new()
-        .add_attribute("action", "update_admin")
-        .add_attribute("new_admin", new_admin))
+/// Checks a registration imported from a signed payload is one the admin
+/// could have registered.
+fn validate_import(deps: Deps, msg: &RegisterMsg) -> Result<(), ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    validate_contract_name(&msg.contract_name)?;
+    let version = validate_version(&msg.version)?;
+    validate_registration(&config.validation, msg, &version)?;
+    if !CHAINS.has(deps.storage, &msg.chain_id) {
+        return Err(ContractError::UnknownChain(msg.chain_id.clone()));
+    }
+    Ok(())
 }
 
-#[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
-    match msg {
-        QueryMsg::Admin {} => to_binary(&ADMIN.load(deps.storage)?),
-        QueryMsg::GetRegistration {
-            name,
-            chain_id,
-            version,
-        } => query_get_registration(deps, name, chain_id, version),
-        QueryMsg::GetCodeIdInfo { chain_id, code_id } => {
-            query_get_code_id_info(deps, chain_id, code_id)
-        }
-        QueryMsg::ListRegistrations { name, chain_id } => {
-            query_list_registrations(deps, name, chain_id)
+/// Updates are applied in order and all fail if any one does. They are also
+/// pushed to follower registries. Unregistrations are queued if there is a
+/// timelock, as the admin's are.
+pub fn execute_import_signed(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    payload: Binary,
+    signatures: Vec<PayloadSignature>,
+) -> Result<Response, ContractError> {
+    assert_not_follower(deps.as_ref())?;
+    verify_signatures(deps.as_ref(), &payload, &signatures)?;
+
+    let SignedPayload {
+        chain_id,
+        registry,
+        nonce,
+        updates,
+    } = from_json(&payload)?;
+    if chain_id != env.block.chain_id || registry != env.contract.address.as_str() {
+        return Err(ContractError::WrongRegistry { chain_id, registry });
+    }
+    let expected = IMPORT_NONCE.may_load(deps.storage)?.unwrap_or_default();
+    if nonce != expected {
+        return Err(ContractError::InvalidNonce { expected, nonce });
+    }
+    let delay = timelock_delay(deps.as_ref())?;
+    let mut response = Response::new()
+        .add_attribute("action", "import_signed")
+        .add_attribute("nonce", nonce.to_string())
+        .add_attribute("count", updates.len().to_string());
+    for (index, update) in updates.into_iter().enumerate() {
+        let batch_entry = |error| ContractError::BatchEntry {
+            index,
+            error: Box::new(error),
+        };
+        match (&update, delay) {
+            (RegistryPacket::Register(msg), _) => {
+                validate_import(deps.as_ref(), msg).map_err(batch_entry)?;
+            }
+            (RegistryPacket::Unregister(msg), Some(delay)) => {
+                assert_registered(deps.as_ref(), msg).map_err(batch_entry)?;
+                let attributes = queue_action(
+                    deps.branch(),
+                    &env,
+                    info.sender.clone(),
+                    delay,
+                    TimelockedAction::Unregister(msg.clone()),
+                )?;
+                response = response
+                    .add_attribute("index", index.to_string())
+                    .add_attributes(attributes);
+                continue;
+            }
+            (RegistryPacket::Unregister(_), None) => {}
         }
+        let sync = sync_msgs(deps.branch(), &env, &update)?;
+        let (attributes, event) = apply_update(deps.branch(), update).map_err(batch_entry)?;
+        response = response
+            .add_messages(sync)
+            .add_attribute("index", index.to_string())
+            .add_attributes(attributes)
+            .add_event(event);
     }
+    IMPORT_NONCE.save(deps.storage, &(nonce + 1))?;
+
+    Ok(response)
 }
 
-pub fn query_get_registration(
+/// Checks payload is signed by at least threshold distinct registry signers.
+fn verify_signatures(
     deps: Deps,
-    contract_name: String,
-    chain_id: String,
-    version: Option<String>,
-) -> StdResult<Binary> {
-    let registration = match version {
-        Some(version) => {
-            let code_id = NAME_CHAIN_ID_VERSION_TO_REGISTRATION
-                .load(deps.storage, (&contract_name, &chain_id, &version))
-                .map_err(|_| StdError::GenericErr {
-                    msg: ContractError::NotFound {}.to_string(),
-                })?;
-
-            let registration = NAME_CHAIN_ID_VERSION_TO_REGISTRATION
-                .load(deps.storage, (&contract_name, &chain_id, &version))
-                .map_err(|_| StdError::GenericErr {
-                    msg: ContractError::NotFound {}.to_string(),
-                })?;
-            Ok::<Registration, StdError>(registration)
+    payload: &Binary,
+    signatures: &[PayloadSignature],
+) -> Result<(), ContractError> {
+    let SignerSet { signers, threshold } = SIGNER_SET
+        .may_load(deps.storage)?
+        .ok_or(ContractError::NoSigners {})?;
+    let hash = Sha256::digest(payload.as_slice());
+
+    let mut signed_by: Vec<&Binary> = vec![];
+    for PayloadSignature {
+        public_key,
+        signature,
+    } in signatures
+    {
+        let signer = signers
+            .iter()
+            .find(|signer| signer.public_key == *public_key)
+            .ok_or_else(|| ContractError::UnknownSigner(hex::encode(public_key.as_slice())))?;
+        let valid = match signer.key_type {
+            KeyType::Secp256k1 => deps.api.secp256k1_verify(&hash, signature, public_key)?,
+            KeyType::Ed25519 => deps.api.ed25519_verify(payload, signature, public_key)?,
+        };
+        if !valid {
+            return Err(ContractError::InvalidSignature(hex::encode(
+                public_key.as_slice(),
+            )));
         }
-        None => {
-            let registration = NAME_CHAIN_ID_VERSION_TO_REGISTRATION
-                .prefix((&contract_name, &chain_id))
-                .range(deps.storage, None, None, Order::Descending)
-                .next()
-                .ok_or(StdError::GenericErr {
-                    msg: ContractError::NotFound {}.to_string(),
-                })?
-                .map_err(|_| StdError::GenericErr {
-                    msg: ContractError::NotFound {}.to_string(),
-                })?;
-            Ok(registration.1)
+        if !signed_by.contains(&public_key) {
+            signed_by.push(public_key);
         }
-    }?;
+    }
 
-    to_binary(&GetRegistrationResponse { registration })
+    if signed_by.len() < threshold as usize {
+        return Err(ContractError::InsufficientSignatures(
+            signed_by.len(),
+            threshold,
+        ));
+    }
+    Ok(())
 }
 
-pub fn query_get_code_id_info(deps: Deps, chain_id: String, code_id: u64) -> StdResult<Binary> {
-    // Retrieve registration.
-    let registration = CHAIN_ID_CODE_ID_TO_REGISTRATION
-        .load(deps.storage, (&chain_id, code_id))
-        .map_err(|_| StdError::GenericErr {
-            msg: ContractError::NotFound {}.to_string(),
-        })?;
+pub fn execute_set_signers(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    signers: Vec<Signer>,
+    threshold: u32,
+) -> Result<Response, ContractError> {
+    if info.sender != CONFIG.load(deps.storage)?.admin {
+        return Err(ContractError::UnauthorizedSetSigners {});
+    }
+
+    if threshold == 0 || threshold as usize > signers.len() {
+        return Err(ContractError::InvalidSignerThreshold(
+            threshold,
+            signers.len(),
+        ));
+    }
+    for (index, signer) in signers.iter().enumerate() {
+        if signers[..index]
+            .iter()
+            .any(|other| other.public_key == signer.public_key)
+        {
+            return Err(ContractError::DuplicateSigner(hex::encode(
+                signer.public_key.as_slice(),
+            )));
+        }
+    }
+
+    // Signers can import unregistrations, so they are timelocked like them.
+    if let Some(delay) = timelock_delay(deps.as_ref())? {
+        let attributes = queue_action(
+            deps,
+            &env,
+            info.sender,
+            delay,
+            TimelockedAction::SetSigners { signers, threshold },
+        )?;
+        return Ok(Response::new()
+            .add_attribute("action", "queue_set_signers")
+            .add_attributes(attributes));
+    }
+
+    let count = signers.len();
+    SIGNER_SET.save(deps.storage, &SignerSet { signers, threshold })?;
 
-    to_binary(&GetRegistrationResponse { registration })
+    Ok(Response::new()
+        .add_attribute("action", "set_signers")
+        .add_attribute("signers", count.to_string())
+        .add_attribute("threshold", threshold.to_string()))
 }
 
-// TODO: Paginate.
-pub fn query_list_registrations(deps: Deps, name: String, chain_id: String) -> StdResult<Binary> {
-    let registrations = CHAIN_ID_CODE_ID_TO_REGISTRATION
-        .prefix(&chain_id)
-        .range(deps.storage, None, None, Order::Ascending)
-        .collect::<StdResult<Vec<(u64, Registration)>>>()?
-        .into_iter()
-        .map(|(_, registration)| registration)
-        .collect();
-    to_binary(&ListRegistrationsResponse { registrations })
+#[allow(clippy::too_many_arguments)]
+pub fn execute_instantiate_by_name(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    name: String,
+    version_req: Option<String>,
+    msg: Binary,
+    label: String,
+    admin: Option<String>,
+    funds: Vec<Coin>,
+) -> Result<Response, ContractError> {
+    let version_req = version_req
+        .map(|version_req| validate_version_req(&version_req))
+        .transpose()?;
+    let admin = admin
+        .map(|admin| deps.api.addr_validate(&admin))
+        .transpose()?;
+
+    assert_funds_match(info.funds, &funds)?;
+
+    let registration = latest_registration(deps.as_ref(), &name, &env.block.chain_id, |version| {
+        version_req
+            .as_ref()
+            .is_none_or(|version_req| version_req.matches(version))
+    })?
+    .ok_or(ContractError::NotFound {})?;
+
+    let reply_id = save_pending_instantiation(
+        deps,
+        &PendingInstantiation {
+            registration: registration.clone(),
+            sender: info.sender,
+        },
+    )?;
+
+    let instantiate = WasmMsg::Instantiate {
+        admin: admin.map(String::from),
+        code_id: registration.code_id,
+        msg,
+        funds,
+        label,
+    };
+
+    Ok(Response::new()
+        .add_attribute("action", "instantiate_by_name")
+        .add_attribute("contract_name", registration.contract_name)
+        .add_attribute("version", registration.version)
+        .add_attribute("code_id", registration.code_id.to_string())
+        .add_submessage(SubMsg::reply_on_success(instantiate, reply_id)))
+}
+
+/// Saves an instantiation for its reply, returning the reply ID to
+/// instantiate with.
+fn save_pending_instantiation(deps: DepsMut, pending: &PendingInstantiation) -> StdResult<u64> {
+    let id = NEXT_REPLY_ID.may_load(deps.storage)?.unwrap_or_default();
+    NEXT_REPLY_ID.save(deps.storage, &(id + 1))?;
+    PENDING_INSTANTIATIONS.save(deps.storage, id, pending)?;
+    Ok(id)
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn execute_instantiate2_by_name(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    name: String,
+    version: String,
+    msg: Binary,
+    label: String,
+    admin: Option<String>,
+    funds: Vec<Coin>,
+    salt: Binary,
+) -> Result<Response, ContractError> {
+    let admin = admin
+        .map(|admin| deps.api.addr_validate(&admin))
+        .transpose()?;
+
+    assert_funds_match(info.funds, &funds)?;
+
+    let registration = NAME_CHAIN_ID_VERSION_TO_REGISTRATION
+        .may_load(deps.storage, (&name, &env.block.chain_id, &version))?
+        .ok_or(ContractError::NotFound {})?;
+    let predicted_address = predict_address(
+        deps.as_ref(),
+        &registration,
+        env.contract.address.as_str(),
+        &salt,
+    )?;
+
+    let reply_id = save_pending_instantiation(
+        deps,
+        &PendingInstantiation {
+            registration: registration.clone(),
+            sender: info.sender,
+        },
+    )?;
+
+    let instantiate = WasmMsg::Instantiate2 {
+        admin: admin.map(String::from),
+        code_id: registration.code_id,
+        label,
+        msg,
+        funds,
+        salt,
+    };
+
+    Ok(Response::new()
+        .add_attribute("action", "instantiate2_by_name")
+        .add_attribute("contract_name", registration.contract_name)
+        .add_attribute("version", registration.version)
+        .add_attribute("code_id", registration.code_id.to_string())
+        .add_attribute("predicted_address", predicted_address)
+        .add_submessage(SubMsg::reply_on_success(instantiate, reply_id)))
+}
+
+/// The registry does not pay for instantiations itself, so the funds sent
+/// must be exactly those requested.
+fn assert_funds_match(sent: Vec<Coin>, requested: &[Coin]) -> Result<(), ContractError> {
+    let mut sent = NativeBalance(sent);
+    sent.normalize();
+    let mut requested = NativeBalance(requested.to_vec());
+    requested.normalize();
+    if sent != requested {
+        return Err(ContractError::IncorrectPaymentAmount {});
+    }
+    Ok(())
+}
+
+/// Computes the address instantiate2 gives a contract instantiated by
+/// creator on the current chain from the registration's checksum.
+fn predict_address(
+    deps: Deps,
+    registration: &Registration,
+    creator: &str,
+    salt: &[u8],
+) -> Result<Addr, ContractError> {
+    let checksum = hex::decode(&registration.checksum)
+        .map_err(|_| ContractError::InvalidChecksum(registration.checksum.clone()))?;
+    let creator = deps.api.addr_canonicalize(creator)?;
+    let address = instantiate2_address(&checksum, &creator, salt)?;
+    Ok(deps.api.addr_humanize(&address)?)
+}
+
+pub fn execute_report_instance(
+    deps: DepsMut,
+    env: Env,
+    address: String,
+) -> Result<Response, ContractError> {
+    let address = deps.api.addr_validate(&address)?;
+    let code_id = deps.querier.query_wasm_contract_info(&address)?.code_id;
+
+    // Only instances of registered code may be recorded.
+    let registration = CHAIN_ID_CODE_ID_TO_REGISTRATION
+        .may_load(deps.storage, (&env.block.chain_id, code_id))?
+        .ok_or(ContractError::NotFound {})?;
+
+    let instantiated_by = INSTANCES
+        .may_load(deps.storage, &address)?
+        .and_then(|instance| instance.instantiated_by);
+    save_instance(
+        deps,
+        &InstanceInfo {
+            address: address.clone(),
+            chain_id: env.block.chain_id,
+            code_id,
+            instantiated_by,
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "report_instance")
+        .add_attribute("address", address)
+        .add_attribute("code_id", code_id.to_string())
+        .add_attribute("contract_name", registration.contract_name)
+        .add_attribute("version", registration.version))
+}
+
+/// Saves an instance, replacing any instance previously recorded at its
+/// address.
+fn save_instance(deps: DepsMut, instance: &InstanceInfo) -> StdResult<()> {
+    if let Some(previous) = INSTANCES.may_load(deps.storage, &instance.address)? {
+        CHAIN_ID_CODE_ID_INSTANCES.remove(
+            deps.storage,
+            (&previous.chain_id, previous.code_id, &previous.address),
+        );
+    }
+    INSTANCES.save(deps.storage, &instance.address, instance)?;
+    CHAIN_ID_CODE_ID_INSTANCES.save(
+        deps.storage,
+        (&instance.chain_id, instance.code_id, &instance.address),
+        &Empty {},
+    )
+}
+
+pub fn execute_add_migration_edge(
+    deps: DepsMut,
+    info: MessageInfo,
+    contract_name: String,
+    from_version: String,
+    to_version: String,
+    migrate_msg: Option<String>,
+    breaking: bool,
+) -> Result<Response, ContractError> {
+    validate_contract_name(&contract_name)?;
+    if validate_version(&from_version)? >= validate_version(&to_version)? {
+        return Err(ContractError::InvalidMigrationEdge(
+            from_version,
+            to_version,
+        ));
+    }
+
+    assert_can_publish(deps.as_ref(), &info.sender, &contract_name)?;
+
+    let edge = MigrationEdge {
+        contract_name: contract_name.clone(),
+        from_version: from_version.clone(),
+        to_version: to_version.clone(),
+        migrate_msg,
+        breaking,
+    };
+    MIGRATION_EDGES.save(
+        deps.storage,
+        (&contract_name, &from_version, &to_version),
+        &edge,
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "add_migration_edge")
+        .add_attribute("contract_name", contract_name)
+        .add_attribute("from_version", from_version)
+        .add_attribute("to_version", to_version)
+        .add_attribute("breaking", breaking.to_string()))
+}
+
+pub fn execute_remove_migration_edge(
+    deps: DepsMut,
+    info: MessageInfo,
+    contract_name: String,
+    from_version: String,
+    to_version: String,
+) -> Result<Response, ContractError> {
+    assert_can_publish(deps.as_ref(), &info.sender, &contract_name)?;
+
+    if !MIGRATION_EDGES.has(deps.storage, (&contract_name, &from_version, &to_version)) {
+        return Err(ContractError::NotFound {});
+    }
+    MIGRATION_EDGES.remove(deps.storage, (&contract_name, &from_version, &to_version));
+
+    Ok(Response::new()
+        .add_attribute("action", "remove_migration_edge")
+        .add_attribute("contract_name", contract_name)
+        .add_attribute("from_version", from_version)
+        .add_attribute("to_version", to_version))
+}
+
+pub fn execute_migrate_to_version(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    contract_addr: String,
+    name: String,
+    version: String,
+    msg: Binary,
+) -> Result<Response, ContractError> {
+    let contract_addr = deps.api.addr_validate(&contract_addr)?;
+    let instance = INSTANCES.may_load(deps.storage, &contract_addr)?;
+    if info.sender != CONFIG.load(deps.storage)?.admin
+        && instance.as_ref().and_then(|i| i.instantiated_by.as_ref()) != Some(&info.sender)
+    {
+        return Err(ContractError::UnauthorizedMigration {});
+    }
+
+    let target = NAME_CHAIN_ID_VERSION_TO_REGISTRATION
+        .may_load(deps.storage, (&name, &env.block.chain_id, &version))?
+        .ok_or(ContractError::NotFound {})?;
+
+    let contract_info = deps.querier.query_wasm_contract_info(&contract_addr)?;
+    if contract_info.admin.as_deref() != Some(env.contract.address.as_str()) {
+        return Err(ContractError::RegistryNotContractAdmin(
+            contract_addr.into_string(),
+        ));
+    }
+
+    // Only migrate contracts onto newer code of the same contract.
+    let current = CHAIN_ID_CODE_ID_TO_REGISTRATION
+        .may_load(deps.storage, (&env.block.chain_id, contract_info.code_id))?
+        .ok_or_else(|| {
+            ContractError::UnregisteredContractCode(
+                contract_addr.to_string(),
+                contract_info.code_id,
+            )
+        })?;
+    if current.contract_name != target.contract_name {
+        return Err(ContractError::MigrationNameMismatch(
+            contract_addr.into_string(),
+            current.contract_name,
+            target.contract_name,
+        ));
+    }
+    if validate_version(&target.version)? <= validate_version(&current.version)? {
+        return Err(ContractError::MigrationNotNewer(
+            contract_addr.into_string(),
+            current.version,
+            target.version,
+        ));
+    }
+
+    if let Some(instance) = instance {
+        save_instance(
+            deps,
+            &InstanceInfo {
+                code_id: target.code_id,
+                ..instance
+            },
+        )?;
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "migrate_to_version")
+        .add_attribute("contract_addr", &contract_addr)
+        .add_attribute("contract_name", target.contract_name)
+        .add_attribute("from_version", current.version)
+        .add_attribute("to_version", target.version)
+        .add_attribute("code_id", target.code_id.to_string())
+        .add_message(WasmMsg::Migrate {
+            contract_addr: contract_addr.into_string(),
+            new_code_id: target.code_id,
+            msg,
+        }))
+}
+
+pub fn execute_set_dependencies(
+    deps: DepsMut,
+    info: MessageInfo,
+    contract_name: String,
+    version: String,
+    dependencies: Vec<Dependency>,
+) -> Result<Response, ContractError> {
+    validate_contract_name(&contract_name)?;
+    validate_version(&version)?;
+    for dependency in dependencies.iter() {
+        validate_contract_name(&dependency.contract_name)?;
+        validate_version_req(&dependency.version_req)?;
+    }
+
+    assert_can_publish(deps.as_ref(), &info.sender, &contract_name)?;
+
+    if dependencies.is_empty() {
+        DEPENDENCIES.remove(deps.storage, (&contract_name, &version));
+    } else {
+        DEPENDENCIES.save(deps.storage, (&contract_name, &version), &dependencies)?;
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "set_dependencies")
+        .add_attribute("contract_name", contract_name)
+        .add_attribute("version", version)
+        .add_attribute("dependencies", dependencies.len().to_string()))
+}
+
+pub fn execute_set_interfaces(
+    deps: DepsMut,
+    info: MessageInfo,
+    contract_name: String,
+    version: String,
+    interfaces: Vec<Interface>,
+) -> Result<Response, ContractError> {
+    validate_contract_name(&contract_name)?;
+    validate_version(&version)?;
+    // Interface names follow the same grammar as contract names.
+    for interface in interfaces.iter() {
+        validate_contract_name(&interface.name)?;
+        validate_version(&interface.version)?;
+    }
+
+    assert_can_publish(deps.as_ref(), &info.sender, &contract_name)?;
+
+    for previous in INTERFACES
+        .may_load(deps.storage, (&contract_name, &version))?
+        .unwrap_or_default()
+    {
+        INTERFACE_IMPLEMENTATIONS.remove(deps.storage, (&previous.name, &contract_name, &version));
+    }
+    for interface in interfaces.iter() {
+        INTERFACE_IMPLEMENTATIONS.save(
+            deps.storage,
+            (&interface.name, &contract_name, &version),
+            &interface.version,
+        )?;
+    }
+    if interfaces.is_empty() {
+        INTERFACES.remove(deps.storage, (&contract_name, &version));
+    } else {
+        INTERFACES.save(deps.storage, (&contract_name, &version), &interfaces)?;
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "set_interfaces")
+        .add_attribute("contract_name", contract_name)
+        .add_attribute("version", version)
+        .add_attribute("interfaces", interfaces.len().to_string()))
+}
+
+pub fn execute_set_schemas(
+    deps: DepsMut,
+    info: MessageInfo,
+    contract_name: String,
+    version: String,
+    schemas: MessageSchemas,
+) -> Result<Response, ContractError> {
+    validate_contract_name(&contract_name)?;
+    validate_version(&version)?;
+    for hash in [
+        schemas.instantiate_msg.as_ref(),
+        schemas.execute_msg.as_ref(),
+        schemas.query_msg.as_ref(),
+        schemas.migrate_msg.as_ref(),
+    ]
+    .iter()
+    .flatten()
+    {
+        validate_schema_hash(hash)?;
+    }
+
+    assert_can_publish(deps.as_ref(), &info.sender, &contract_name)?;
+
+    SCHEMAS.save(deps.storage, (&contract_name, &version), &schemas)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_schemas")
+        .add_attribute("contract_name", contract_name)
+        .add_attribute("version", version))
+}
+
+pub fn execute_set_trusted_channel(
+    deps: DepsMut,
+    info: MessageInfo,
+    channel_id: Option<String>,
+) -> Result<Response, ContractError> {
+    if info.sender != CONFIG.load(deps.storage)?.admin {
+        return Err(ContractError::UnauthorizedSetTrustedChannel {});
+    }
+
+    match &channel_id {
+        Some(channel_id) => {
+            if !SYNC_CHANNELS.has(deps.storage, channel_id) {
+                return Err(ContractError::UnknownChannel(channel_id.clone()));
+            }
+            TRUSTED_CHANNEL.save(deps.storage, channel_id)?;
+        }
+        None => TRUSTED_CHANNEL.remove(deps.storage),
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "set_trusted_channel")
+        .add_attribute(
+            "channel_id",
+            channel_id.unwrap_or_else(|| "none".to_string()),
+        ))
+}
+
+pub fn execute_add_sync_peer(
+    deps: DepsMut,
+    info: MessageInfo,
+    connection_id: String,
+    port_id: String,
+) -> Result<Response, ContractError> {
+    if info.sender != CONFIG.load(deps.storage)?.admin {
+        return Err(ContractError::UnauthorizedSyncPeer {});
+    }
+    SYNC_PEERS.save(deps.storage, (&connection_id, &port_id), &Empty {})?;
+
+    Ok(Response::new()
+        .add_attribute("action", "add_sync_peer")
+        .add_attribute("connection_id", connection_id)
+        .add_attribute("port_id", port_id))
+}
+
+pub fn execute_remove_sync_peer(
+    deps: DepsMut,
+    info: MessageInfo,
+    connection_id: String,
+    port_id: String,
+) -> Result<Response, ContractError> {
+    if info.sender != CONFIG.load(deps.storage)?.admin {
+        return Err(ContractError::UnauthorizedSyncPeer {});
+    }
+    SYNC_PEERS.remove(deps.storage, (&connection_id, &port_id));
+
+    Ok(Response::new()
+        .add_attribute("action", "remove_sync_peer")
+        .add_attribute("connection_id", connection_id)
+        .add_attribute("port_id", port_id))
+}
+
+pub fn execute_close_sync_channel(
+    deps: DepsMut,
+    info: MessageInfo,
+    channel_id: String,
+) -> Result<Response, ContractError> {
+    if info.sender != CONFIG.load(deps.storage)?.admin {
+        return Err(ContractError::UnauthorizedSyncPeer {});
+    }
+    if !SYNC_CHANNELS.has(deps.storage, &channel_id) {
+        return Err(ContractError::UnknownChannel(channel_id));
+    }
+    remove_sync_channel(deps.storage, &channel_id)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "close_sync_channel")
+        .add_attribute("channel_id", &channel_id)
+        .add_message(IbcMsg::CloseChannel { channel_id }))
+}
+
+pub fn execute_resync(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    channel_id: String,
+) -> Result<Response, ContractError> {
+    if info.sender != CONFIG.load(deps.storage)?.admin {
+        return Err(ContractError::UnauthorizedResync {});
+    }
+    if !SYNC_CHANNELS.has(deps.storage, &channel_id) {
+        return Err(ContractError::UnknownChannel(channel_id));
+    }
+
+    let updates = UNDELIVERED_UPDATES
+        .prefix(&channel_id)
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<(u64, RegistryPacket)>>>()?;
+    let msgs = updates
+        .into_iter()
+        .map(|(sequence, update)| {
+            UNDELIVERED_UPDATES.remove(deps.storage, (&channel_id, sequence));
+            sync_msg(&env, channel_id.clone(), &SyncPacket { sequence, update })
+        })
+        .collect::<StdResult<Vec<IbcMsg>>>()?;
+
+    Ok(Response::new()
+        .add_attribute("action", "resync")
+        .add_attribute("channel_id", channel_id)
+        .add_attribute("count", msgs.len().to_string())
+        .add_messages(msgs))
+}
+
+/// Whether sender is the guardian or, if the guardian is a cw4 group, one
+/// of its members.
+fn is_guardian(deps: Deps, sender: &Addr) -> StdResult<bool> {
+    match CONFIG.load(deps.storage)?.guardian {
+        Some(Guardian::Address(guardian)) => Ok(*sender == guardian),
+        Some(Guardian::Cw4Group(group)) => Ok(Cw4Contract::new(group)
+            .is_voting_member(&deps.querier, sender, None)?
+            .is_some()),
+        None => Ok(false),
+    }
+}
+
+fn is_admin_or_guardian(deps: Deps, sender: &Addr) -> StdResult<bool> {
+    Ok(*sender == CONFIG.load(deps.storage)?.admin || is_guardian(deps, sender)?)
+}
+
+pub fn execute_pause(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    until: Option<Expiration>,
+) -> Result<Response, ContractError> {
+    if !is_admin_or_guardian(deps.as_ref(), &info.sender)? {
+        return Err(ContractError::UnauthorizedPause {});
+    }
+
+    let until = until.unwrap_or(Expiration::Never {});
+    let mut config = CONFIG.load(deps.storage)?;
+    // Only the admin may end a pause early, so the guardian may only
+    // extend an active pause.
+    if let Some(paused_until) = config.paused_until {
+        if info.sender != config.admin
+            && !paused_until.is_expired(&env.block)
+            && !matches!(
+                until.partial_cmp(&paused_until),
+                Some(Ordering::Greater | Ordering::Equal)
+            )
+        {
+            return Err(ContractError::PauseNotExtended(paused_until));
+        }
+    }
+    config.paused_until = Some(until);
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "pause")
+        .add_attribute("until", until.to_string()))
+}
+
+pub fn execute_unpause(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    if info.sender != CONFIG.load(deps.storage)?.admin {
+        return Err(ContractError::UnauthorizedPause {});
+    }
+
+    CONFIG.update(deps.storage, |mut config| -> StdResult<_> {
+        config.paused_until = None;
+        Ok(config)
+    })?;
+
+    Ok(Response::new().add_attribute("action", "unpause"))
+}
+
+/// Freezing rejects every open registration proposal, refunding its fee,
+/// as proposals may no longer be approved or rejected once frozen.
+pub fn execute_freeze(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    if info.sender != CONFIG.load(deps.storage)?.admin {
+        return Err(ContractError::UnauthorizedPause {});
+    }
+
+    CONFIG.update(deps.storage, |mut config| -> StdResult<_> {
+        config.frozen = true;
+        Ok(config)
+    })?;
+
+    let proposals = REGISTRATION_PROPOSALS
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, proposal)| proposal))
+        .collect::<StdResult<Vec<RegistrationProposal>>>()?;
+    let mut response = Response::new()
+        .add_attribute("action", "freeze")
+        .add_attribute("rejected_proposals", proposals.len().to_string());
+    for proposal in proposals {
+        REGISTRATION_PROPOSALS.remove(deps.storage, proposal.id);
+        response = response.add_messages(pay_fee(&proposal.proposer, proposal.fee));
+    }
+
+    Ok(response)
+}
+
+/// The timelock delay, if actions are timelocked.
+fn timelock_delay(deps: Deps) -> StdResult<Option<u64>> {
+    let delay = CONFIG.load(deps.storage)?.timelock_delay;
+    Ok(Some(delay).filter(|delay| *delay > 0))
+}
+
+/// Queues action to be executable once delay blocks have passed, returning
+/// attributes describing it.
+fn queue_action(
+    deps: DepsMut,
+    env: &Env,
+    proposer: Addr,
+    delay: u64,
+    action: TimelockedAction,
+) -> Result<Vec<Attribute>, ContractError> {
+    let id = NEXT_ACTION_ID.may_load(deps.storage)?.unwrap_or_default();
+    NEXT_ACTION_ID.save(deps.storage, &(id + 1))?;
+
+    let executable_at = executable_at(env, delay)?;
+    QUEUED_ACTIONS.save(
+        deps.storage,
+        id,
+        &QueuedAction {
+            id,
+            action,
+            proposer,
+            executable_at,
+        },
+    )?;
+
+    Ok(vec![
+        attr("action_id", id.to_string()),
+        attr("executable_at", executable_at.to_string()),
+    ])
+}
+
+/// The height an action queued now with delay may be executed at.
+fn executable_at(env: &Env, delay: u64) -> Result<u64, ContractError> {
+    Ok(Uint64::from(env.block.height)
+        .checked_add(Uint64::from(delay))?
+        .u64())
+}
+
+/// Delays too long to queue actions with are rejected, so the timelock
+/// may always be changed again.
+pub fn execute_set_timelock_delay(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    blocks: u64,
+) -> Result<Response, ContractError> {
+    if info.sender != CONFIG.load(deps.storage)?.admin {
+        return Err(ContractError::UnauthorizedSetTimelockDelay {});
+    }
+    executable_at(&env, blocks)?;
+
+    if let Some(delay) = timelock_delay(deps.as_ref())? {
+        let attributes = queue_action(
+            deps,
+            &env,
+            info.sender,
+            delay,
+            TimelockedAction::SetTimelockDelay { blocks },
+        )?;
+        return Ok(Response::new()
+            .add_attribute("action", "queue_set_timelock_delay")
+            .add_attributes(attributes));
+    }
+
+    save_timelock_delay(deps, blocks)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_timelock_delay")
+        .add_attribute("blocks", blocks.to_string()))
+}
+
+pub fn execute_execute_queued(
+    mut deps: DepsMut,
+    env: Env,
+    id: u64,
+) -> Result<Response, ContractError> {
+    let QueuedAction {
+        action,
+        executable_at,
+        ..
+    } = QUEUED_ACTIONS
+        .may_load(deps.storage, id)?
+        .ok_or(ContractError::NotFound {})?;
+    if env.block.height < executable_at {
+        return Err(ContractError::ActionNotReady { id, executable_at });
+    }
+    QUEUED_ACTIONS.remove(deps.storage, id);
+
+    let response = Response::new()
+        .add_attribute("action", "execute_queued")
+        .add_attribute("action_id", id.to_string());
+    match action {
+        TimelockedAction::Unregister(msg) => {
+            // Unlike admin changes, unregistrations wait out a pause.
+            assert_writable(deps.as_ref(), &env)?;
+            assert_not_follower(deps.as_ref())?;
+            let sync = sync_msgs(
+                deps.branch(),
+                &env,
+                &RegistryPacket::Unregister(msg.clone()),
+            )?;
+            let (attributes, event) = remove_registration(deps, msg)?;
+            Ok(response
+                .add_messages(sync)
+                .add_attribute("queued_action", "unregister")
+                .add_attributes(attributes)
+                .add_event(event))
+        }
+        TimelockedAction::UpdateAdmin { admin } => {
+            let old_admin = CONFIG.load(deps.storage)?.admin;
+            save_admin(deps, admin.clone())?;
+            Ok(response
+                .add_attribute("queued_action", "update_admin")
+                .add_attribute("new_admin", &admin)
+                .add_event(update_admin_event(&old_admin, &admin)))
+        }
+        TimelockedAction::SetTimelockDelay { blocks } => {
+            save_timelock_delay(deps, blocks)?;
+            Ok(response
+                .add_attribute("queued_action", "set_timelock_delay")
+                .add_attribute("blocks", blocks.to_string()))
+        }
+        TimelockedAction::SetGuardian { guardian } => {
+            save_guardian(deps, guardian)?;
+            Ok(response.add_attribute("queued_action", "set_guardian"))
+        }
+        TimelockedAction::SetSigners { signers, threshold } => {
+            let count = signers.len();
+            SIGNER_SET.save(deps.storage, &SignerSet { signers, threshold })?;
+            Ok(response
+                .add_attribute("queued_action", "set_signers")
+                .add_attribute("signers", count.to_string())
+                .add_attribute("threshold", threshold.to_string()))
+        }
+    }
+}
+
+fn save_admin(deps: DepsMut, admin: Addr) -> StdResult<Config> {
+    CONFIG.update(deps.storage, |mut config| -> StdResult<_> {
+        config.admin = admin;
+        Ok(config)
+    })
+}
+
+fn save_timelock_delay(deps: DepsMut, blocks: u64) -> StdResult<Config> {
+    CONFIG.update(deps.storage, |mut config| -> StdResult<_> {
+        config.timelock_delay = blocks;
+        Ok(config)
+    })
+}
+
+fn save_guardian(deps: DepsMut, guardian: Option<Guardian>) -> StdResult<Config> {
+    CONFIG.update(deps.storage, |mut config| -> StdResult<_> {
+        config.guardian = guardian;
+        Ok(config)
+    })
+}
+
+pub fn execute_set_guardian(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    guardian: Option<UncheckedGuardian>,
+) -> Result<Response, ContractError> {
+    if info.sender != CONFIG.load(deps.storage)?.admin {
+        return Err(ContractError::UnauthorizedSetGuardian {});
+    }
+
+    let guardian = match guardian {
+        Some(UncheckedGuardian::Address(address)) => {
+            Some(Guardian::Address(deps.api.addr_validate(&address)?))
+        }
+        Some(UncheckedGuardian::Cw4Group(group)) => {
+            let group = Cw4Contract::new(deps.api.addr_validate(&group)?);
+            // Fail early if this is not a cw4 group.
+            group.total_weight(&deps.querier)?;
+            Some(Guardian::Cw4Group(group.addr()))
+        }
+        None => None,
+    };
+
+    if let Some(delay) = timelock_delay(deps.as_ref())? {
+        let attributes = queue_action(
+            deps,
+            &env,
+            info.sender,
+            delay,
+            TimelockedAction::SetGuardian { guardian },
+        )?;
+        return Ok(Response::new()
+            .add_attribute("action", "queue_set_guardian")
+            .add_attributes(attributes));
+    }
+
+    save_guardian(deps, guardian)?;
+
+    Ok(Response::new().add_attribute("action", "set_guardian"))
+}
+
+pub fn execute_cancel_queued(
+    deps: DepsMut,
+    info: MessageInfo,
+    id: u64,
+) -> Result<Response, ContractError> {
+    if !is_admin_or_guardian(deps.as_ref(), &info.sender)? {
+        return Err(ContractError::UnauthorizedCancel {});
+    }
+
+    let queued = QUEUED_ACTIONS
+        .may_load(deps.storage, id)?
+        .ok_or(ContractError::NotFound {})?;
+    // Otherwise the guardian could veto its own replacement.
+    if matches!(queued.action, TimelockedAction::SetGuardian { .. })
+        && info.sender != CONFIG.load(deps.storage)?.admin
+    {
+        return Err(ContractError::UnauthorizedCancelSetGuardian {});
+    }
+    QUEUED_ACTIONS.remove(deps.storage, id);
+
+    Ok(Response::new()
+        .add_attribute("action", "cancel_queued")
+        .add_attribute("action_id", id.to_string()))
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn execute_delegate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    delegate: String,
+    names: Vec<String>,
+    max_registrations: u64,
+    period: u64,
+    expiration: Option<Expiration>,
+) -> Result<Response, ContractError> {
+    if info.sender != CONFIG.load(deps.storage)?.admin {
+        return Err(ContractError::UnauthorizedDelegate {});
+    }
+
+    let delegate = deps.api.addr_validate(&delegate)?;
+    for name in &names {
+        validate_contract_name(name)?;
+        // The admin may only delegate what it may publish itself.
+        if namespace_of(name).is_some() {
+            return Err(ContractError::NamespacedDelegation(name.clone()));
+        }
+    }
+    if period == 0 {
+        return Err(ContractError::InvalidPeriod {});
+    }
+
+    DELEGATIONS.save(
+        deps.storage,
+        &delegate,
+        &Delegation {
+            delegate: delegate.clone(),
+            names,
+            max_registrations,
+            period,
+            expiration: expiration.unwrap_or(Expiration::Never {}),
+            period_start: env.block.height,
+            used: 0,
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "delegate")
+        .add_attribute("delegate", delegate)
+        .add_attribute("max_registrations", max_registrations.to_string())
+        .add_attribute("period", period.to_string()))
+}
+
+pub fn execute_revoke_delegation(
+    deps: DepsMut,
+    info: MessageInfo,
+    delegate: String,
+) -> Result<Response, ContractError> {
+    if info.sender != CONFIG.load(deps.storage)?.admin {
+        return Err(ContractError::UnauthorizedDelegate {});
+    }
+
+    let delegate = deps.api.addr_validate(&delegate)?;
+    if !DELEGATIONS.has(deps.storage, &delegate) {
+        return Err(ContractError::NotFound {});
+    }
+    DELEGATIONS.remove(deps.storage, &delegate);
+
+    Ok(Response::new()
+        .add_attribute("action", "revoke_delegation")
+        .add_attribute("delegate", delegate))
+}
+
+pub fn execute_update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    registration_mode: Option<RegistrationMode>,
+    validation: Option<ValidationRules>,
+    registration_fee: Option<Vec<Coin>>,
+    rate_limit: Option<RateLimitUpdate>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    if info.sender != config.admin {
+        return Err(ContractError::UnauthorizedUpdateConfig {});
+    }
+
+    if let Some(registration_mode) = registration_mode {
+        config.registration_mode = registration_mode;
+    }
+    if let Some(validation) = validation {
+        if validation.max_name_length == 0
+            || validation.max_name_length as usize > MAX_NAME_SEGMENT_LENGTH
+        {
+            return Err(ContractError::InvalidMaxNameLength(MAX_NAME_SEGMENT_LENGTH));
+        }
+        config.validation = validation;
+    }
+    if let Some(registration_fee) = registration_fee {
+        let mut registration_fee = NativeBalance(registration_fee);
+        registration_fee.normalize();
+        config.registration_fee = registration_fee.into_vec();
+    }
+    match rate_limit {
+        Some(RateLimitUpdate::Set(rate_limit)) => {
+            if rate_limit.period == 0 {
+                return Err(ContractError::InvalidPeriod {});
+            }
+            config.rate_limit = Some(rate_limit);
+        }
+        Some(RateLimitUpdate::Remove {}) => config.rate_limit = None,
+        None => {}
+    }
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("action", "update_config"))
+}
+
+/// Approving a proposal forwards its fee to the admin.
+pub fn execute_approve_registration(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+) -> Result<Response, ContractError> {
+    let admin = CONFIG.load(deps.storage)?.admin;
+    if info.sender != admin {
+        return Err(ContractError::UnauthorizedApproveRegistration {});
+    }
+
+    let proposal = REGISTRATION_PROPOSALS
+        .may_load(deps.storage, id)?
+        .ok_or(ContractError::NotFound {})?;
+    REGISTRATION_PROPOSALS.remove(deps.storage, id);
+
+    assert_not_follower(deps.as_ref())?;
+    let msg = proposal.registration;
+    let sync = sync_msgs(deps.branch(), &env, &RegistryPacket::Register(msg.clone()))?;
+    let (attributes, event) = store_registration(deps.branch(), msg)?;
+
+    Ok(Response::new()
+        .add_messages(sync)
+        .add_messages(pay_fee(&admin, proposal.fee))
+        .add_attribute("action", "approve_registration")
+        .add_attribute("proposal_id", id.to_string())
+        .add_attribute("proposer", proposal.proposer)
+        .add_attributes(attributes)
+        .add_event(event))
+}
+
+/// Rejecting a proposal refunds its fee to the proposer.
+pub fn execute_reject_registration(
+    deps: DepsMut,
+    info: MessageInfo,
+    id: u64,
+) -> Result<Response, ContractError> {
+    if info.sender != CONFIG.load(deps.storage)?.admin {
+        return Err(ContractError::UnauthorizedApproveRegistration {});
+    }
+
+    let proposal = REGISTRATION_PROPOSALS
+        .may_load(deps.storage, id)?
+        .ok_or(ContractError::NotFound {})?;
+    REGISTRATION_PROPOSALS.remove(deps.storage, id);
+
+    Ok(Response::new()
+        .add_messages(pay_fee(&proposal.proposer, proposal.fee))
+        .add_attribute("action", "reject_registration")
+        .add_attribute("proposal_id", id.to_string()))
+}
+
+pub fn execute_update_admin(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    new_admin: String,
+) -> Result<Response, ContractError> {
+    let current_admin = CONFIG.load(deps.storage)?.admin;
+    if info.sender != current_admin {
+        return Err(ContractError::UnauthorizedUpdateAdmin {});
+    }
+
+    let validated_admin = deps.api.addr_validate(&new_admin)?;
+    if let Some(delay) = timelock_delay(deps.as_ref())? {
+        let attributes = queue_action(
+            deps,
+            &env,
+            info.sender,
+            delay,
+            TimelockedAction::UpdateAdmin {
+                admin: validated_admin,
+            },
+        )?;
+        return Ok(Response::new()
+            .add_attribute("action", "queue_update_admin")
+            .add_attributes(attributes));
+    }
+
+    save_admin(deps, validated_admin.clone())?;
+
+    Ok(Response::new()
+        .add_attribute("action", "update_admin")
+        .add_attribute("old_admin", &current_admin)
+        .add_attribute("new_admin", new_admin)
+        .add_event(update_admin_event(&current_admin, &validated_admin)))
+}
+
+pub fn execute_add_chain(
+    deps: DepsMut,
+    info: MessageInfo,
+    chain_id: String,
+    bech32_prefix: String,
+    pretty_name: String,
+    is_testnet: bool,
+) -> Result<Response, ContractError> {
+    let admin = CONFIG.load(deps.storage)?.admin;
+    if info.sender != admin {
+        return Err(ContractError::UnauthorizedAddChain {});
+    }
+
+    if chain_id.is_empty()
+        || chain_id
+            .chars()
+            .any(|c| c.is_whitespace() || c.is_control())
+    {
+        return Err(ContractError::InvalidChainId(chain_id));
+    }
+    if bech32_prefix.is_empty()
+        || !bech32_prefix
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+    {
+        return Err(ContractError::InvalidBech32Prefix(bech32_prefix));
+    }
+    if CHAINS.has(deps.storage, &chain_id) {
+        return Err(ContractError::ChainAlreadyExists(chain_id));
+    }
+
+    let chain = ChainInfo {
+        chain_id: chain_id.clone(),
+        bech32_prefix: bech32_prefix.clone(),
+        pretty_name,
+        is_testnet,
+    };
+    CHAINS.save(deps.storage, &chain_id, &chain)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "add_chain")
+        .add_attribute("chain_id", chain_id)
+        .add_attribute("bech32_prefix", bech32_prefix)
+        .add_attribute("is_testnet", is_testnet.to_string()))
+}
+
+pub fn execute_register_namespace(
+    deps: DepsMut,
+    info: MessageInfo,
+    namespace: String,
+    owner: String,
+) -> Result<Response, ContractError> {
+    let admin = CONFIG.load(deps.storage)?.admin;
+    if info.sender != admin {
+        return Err(ContractError::UnauthorizedRegisterNamespace {});
+    }
+
+    validate_namespace(&namespace)?;
+    if NAMESPACES.has(deps.storage, &namespace) {
+        return Err(ContractError::NamespaceAlreadyRegistered(namespace));
+    }
+
+    let owner = deps.api.addr_validate(&owner)?;
+    NAMESPACES.save(
+        deps.storage,
+        &namespace,
+        &NamespaceInfo {
+            namespace: namespace.clone(),
+            owner: owner.clone(),
+            delegates: vec![],
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "register_namespace")
+        .add_attribute("namespace", namespace)
+        .add_attribute("owner", owner))
+}
+
+/// Loads a namespace and checks that sender owns it.
+fn load_owned_namespace(
+    deps: Deps,
+    sender: &Addr,
+    namespace: &str,
+) -> Result<NamespaceInfo, ContractError> {
+    let namespace_info = NAMESPACES
+        .may_load(deps.storage, namespace)?
+        .ok_or_else(|| ContractError::UnknownNamespace(namespace.to_string()))?;
+    if *sender != namespace_info.owner {
+        return Err(ContractError::UnauthorizedUpdateNamespace {});
+    }
+    Ok(namespace_info)
+}
+
+pub fn execute_update_namespace_owner(
+    deps: DepsMut,
+    info: MessageInfo,
+    namespace: String,
+    owner: String,
+) -> Result<Response, ContractError> {
+    let mut namespace_info = load_owned_namespace(deps.as_ref(), &info.sender, &namespace)?;
+
+    namespace_info.owner = deps.api.addr_validate(&owner)?;
+    NAMESPACES.save(deps.storage, &namespace, &namespace_info)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "update_namespace_owner")
+        .add_attribute("namespace", namespace)
+        .add_attribute("new_owner", owner))
+}
+
+pub fn execute_add_namespace_delegate(
+    deps: DepsMut,
+    info: MessageInfo,
+    namespace: String,
+    delegate: String,
+) -> Result<Response, ContractError> {
+    let mut namespace_info = load_owned_namespace(deps.as_ref(), &info.sender, &namespace)?;
+
+    let delegate = deps.api.addr_validate(&delegate)?;
+    if !namespace_info.delegates.contains(&delegate) {
+        namespace_info.delegates.push(delegate.clone());
+        NAMESPACES.save(deps.storage, &namespace, &namespace_info)?;
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "add_namespace_delegate")
+        .add_attribute("namespace", namespace)
+        .add_attribute("delegate", delegate))
+}
+
+pub fn execute_remove_namespace_delegate(
+    deps: DepsMut,
+    info: MessageInfo,
+    namespace: String,
+    delegate: String,
+) -> Result<Response, ContractError> {
+    let mut namespace_info = load_owned_namespace(deps.as_ref(), &info.sender, &namespace)?;
+
+    let delegate = deps.api.addr_validate(&delegate)?;
+    namespace_info.delegates.retain(|d| *d != delegate);
+    NAMESPACES.save(deps.storage, &namespace, &namespace_info)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "remove_namespace_delegate")
+        .add_attribute("namespace", namespace)
+        .add_attribute("delegate", delegate))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
+    let PendingInstantiation {
+        registration,
+        sender,
+    } = PENDING_INSTANTIATIONS
+        .may_load(deps.storage, msg.id)?
+        .ok_or(ContractError::UnknownReplyId(msg.id))?;
+    PENDING_INSTANTIATIONS.remove(deps.storage, msg.id);
+
+    let response = parse_reply_instantiate_data(msg)?;
+    let contract_address = deps.api.addr_validate(&response.contract_address)?;
+
+    save_instance(
+        deps,
+        &InstanceInfo {
+            address: contract_address.clone(),
+            chain_id: env.block.chain_id,
+            code_id: registration.code_id,
+            instantiated_by: Some(sender),
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "instantiate_by_name_reply")
+        .add_attribute("contract_address", &contract_address)
+        .set_data(to_json_binary(&InstantiateByNameResponse {
+            contract_address,
+            registration,
+        })?))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Admin {} => to_json_binary(&CONFIG.load(deps.storage)?.admin),
+        QueryMsg::GetRegistration {
+            name,
+            chain_id,
+            version,
+        } => query_get_registration(deps, name, chain_id, version),
+        QueryMsg::GetCodeIdInfo { chain_id, code_id } => {
+            query_get_code_id_info(deps, chain_id, code_id)
+        }
+        QueryMsg::GetRegistrations { requests } => query_get_registrations(deps, requests),
+        QueryMsg::GetCodeIdInfos { chain_id, code_ids } => {
+            query_get_code_id_infos(deps, chain_id, code_ids)
+        }
+        QueryMsg::ListRegistrations { name, chain_id } => {
+            query_list_registrations(deps, name, chain_id)
+        }
+        QueryMsg::PredictAddress {
+            name,
+            version,
+            creator,
+            salt,
+        } => query_predict_address(deps, env, name, version, creator, salt),
+        QueryMsg::ListInstances {
+            chain_id,
+            code_id,
+            start_after,
+            limit,
+        } => query_list_instances(deps, chain_id, code_id, start_after, limit),
+        QueryMsg::GetInstanceRegistration { address } => {
+            query_get_instance_registration(deps, env, address)
+        }
+        QueryMsg::UpgradePath { chain_id, code_id } => query_upgrade_path(deps, chain_id, code_id),
+        QueryMsg::GetDependencies { name, version } => query_get_dependencies(deps, name, version),
+        QueryMsg::ResolveDependencies {
+            name,
+            version,
+            chain_id,
+        } => query_resolve_dependencies(deps, name, version, chain_id),
+        QueryMsg::GetInterfaces { name, version } => query_get_interfaces(deps, name, version),
+        QueryMsg::ListByInterface {
+            interface,
+            chain_id,
+            start_after,
+            limit,
+        } => query_list_by_interface(deps, interface, chain_id, start_after, limit),
+        QueryMsg::GetSchemas { name, version } => query_get_schemas(deps, name, version),
+        QueryMsg::SyncChannels {} => query_sync_channels(deps),
+        QueryMsg::ListUndeliveredUpdates {
+            channel_id,
+            start_after,
+            limit,
+        } => query_list_undelivered_updates(deps, channel_id, start_after, limit),
+        QueryMsg::Signers {} => query_signers(deps),
+        QueryMsg::PauseInfo {} => query_pause_info(deps, env),
+        QueryMsg::Config {} => to_json_binary(&CONFIG.load(deps.storage)?),
+        QueryMsg::ListRegistrationProposals { start_after, limit } => {
+            query_list_registration_proposals(deps, start_after, limit)
+        }
+        QueryMsg::ListDelegations { start_after, limit } => {
+            query_list_delegations(deps, env, start_after, limit)
+        }
+        QueryMsg::ListQueuedActions { start_after, limit } => {
+            query_list_queued_actions(deps, start_after, limit)
+        }
+        QueryMsg::GetChain { chain_id } => query_get_chain(deps, chain_id),
+        QueryMsg::ListChains { is_testnet } => query_list_chains(deps, is_testnet),
+        QueryMsg::GetNamespace { namespace } => query_get_namespace(deps, namespace),
+    }
+}
+
+pub fn query_get_registration(
+    deps: Deps,
+    contract_name: String,
+    chain_id: String,
+    version: Option<String>,
+) -> StdResult<Binary> {
+    let registration = may_load_registration(deps, &contract_name, &chain_id, version.as_deref())?
+        .ok_or(StdError::GenericErr {
+            msg: ContractError::NotFound {}.to_string(),
+        })?;
+
+    to_json_binary(&GetRegistrationResponse { registration })
+}
+
+pub fn query_get_code_id_info(deps: Deps, chain_id: String, code_id: u64) -> StdResult<Binary> {
+    // Retrieve registration.
+    let registration = CHAIN_ID_CODE_ID_TO_REGISTRATION
+        .load(deps.storage, (&chain_id, code_id))
+        .map_err(|_| StdError::GenericErr {
+            msg: ContractError::NotFound {}.to_string(),
+        })?;
+
+    to_json_binary(&GetRegistrationResponse { registration })
+}
+
+pub fn query_get_registrations(
+    deps: Deps,
+    requests: Vec<RegistrationRequest>,
+) -> StdResult<Binary> {
+    let registrations = requests
+        .into_iter()
+        .map(|request| {
+            may_load_registration(
+                deps,
+                &request.name,
+                &request.chain_id,
+                request.version.as_deref(),
+            )
+        })
+        .collect::<StdResult<Vec<Option<Registration>>>>()?;
+
+    to_json_binary(&GetRegistrationsResponse { registrations })
+}
+
+pub fn query_get_code_id_infos(
+    deps: Deps,
+    chain_id: String,
+    code_ids: Vec<u64>,
+) -> StdResult<Binary> {
+    let registrations = code_ids
+        .into_iter()
+        .map(|code_id| {
+            CHAIN_ID_CODE_ID_TO_REGISTRATION.may_load(deps.storage, (&chain_id, code_id))
+        })
+        .collect::<StdResult<Vec<Option<Registration>>>>()?;
+
+    to_json_binary(&GetRegistrationsResponse { registrations })
+}
+
+/// If version provided, loads that version. Otherwise loads the latest
+/// version registered.
+fn may_load_registration(
+    deps: Deps,
+    contract_name: &str,
+    chain_id: &str,
+    version: Option<&str>,
+) -> StdResult<Option<Registration>> {
+    match version {
+        Some(version) => NAME_CHAIN_ID_VERSION_TO_REGISTRATION
+            .may_load(deps.storage, (contract_name, chain_id, version)),
+        None => latest_registration(deps, contract_name, chain_id, |_| true),
+    }
+}
+
+/// Loads the latest version registered for which matches returns true.
+fn latest_registration(
+    deps: Deps,
+    contract_name: &str,
+    chain_id: &str,
+    matches: impl Fn(&Version) -> bool,
+) -> StdResult<Option<Registration>> {
+    // Versions are stored as strings, so find the latest by comparing them
+    // as semver rather than relying on key order.
+    let registration = NAME_CHAIN_ID_VERSION_TO_REGISTRATION
+        .prefix((contract_name, chain_id))
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<(String, Registration)>>>()?
+        .into_iter()
+        .filter_map(|(version, registration)| {
+            Version::parse(&version)
+                .ok()
+                .filter(|version| matches(version))
+                .map(|version| (version, registration))
+        })
+        .max_by(|(a, _), (b, _)| a.cmp(b))
+        .map(|(_, registration)| registration);
+    Ok(registration)
+}
+
+// TODO: Paginate.
+pub fn query_list_registrations(deps: Deps, name: String, chain_id: String) -> StdResult<Binary> {
+    let registrations = NAME_CHAIN_ID_VERSION_TO_REGISTRATION
+        .prefix((&name, &chain_id))
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<(String, Registration)>>>()?
+        .into_iter()
+        .map(|(_, registration)| registration)
+        .collect();
+    to_json_binary(&ListRegistrationsResponse { registrations })
+}
+
+pub fn query_predict_address(
+    deps: Deps,
+    env: Env,
+    name: String,
+    version: String,
+    creator: String,
+    salt: Binary,
+) -> StdResult<Binary> {
+    let registration = NAME_CHAIN_ID_VERSION_TO_REGISTRATION
+        .load(deps.storage, (&name, &env.block.chain_id, &version))
+        .map_err(|_| StdError::GenericErr {
+            msg: ContractError::NotFound {}.to_string(),
+        })?;
+    let address = predict_address(deps, &registration, &creator, &salt)
+        .map_err(|e| StdError::GenericErr { msg: e.to_string() })?;
+
+    to_json_binary(&PredictAddressResponse {
+        address,
+        registration,
+    })
+}
+
+pub fn query_list_instances(
+    deps: Deps,
+    chain_id: String,
+    code_id: u64,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    let start_after = start_after
+        .map(|address| deps.api.addr_validate(&address))
+        .transpose()?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+
+    let instances = CHAIN_ID_CODE_ID_INSTANCES
+        .prefix((&chain_id, code_id))
+        .keys(
+            deps.storage,
+            start_after.as_ref().map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .map(|address| INSTANCES.load(deps.storage, &address?))
+        .collect::<StdResult<Vec<InstanceInfo>>>()?;
+
+    to_json_binary(&ListInstancesResponse { instances })
+}
+
+pub fn query_get_instance_registration(deps: Deps, env: Env, address: String) -> StdResult<Binary> {
+    let code_id = deps.querier.query_wasm_contract_info(address)?.code_id;
+    query_get_code_id_info(deps, env.block.chain_id, code_id)
+}
+
+pub fn query_upgrade_path(deps: Deps, chain_id: String, code_id: u64) -> StdResult<Binary> {
+    let current = CHAIN_ID_CODE_ID_TO_REGISTRATION
+        .load(deps.storage, (&chain_id, code_id))
+        .map_err(|_| StdError::GenericErr {
+            msg: ContractError::NotFound {}.to_string(),
+        })?;
+    let contract_name = current.contract_name.as_str();
+
+    // Breadth first search over versions registered on the chain, so the
+    // first path found to each version is a shortest one. Edges always go
+    // to newer versions, so there are no cycles.
+    let mut steps: BTreeMap<String, (String, UpgradeStep)> = BTreeMap::new();
+    let mut queue = VecDeque::from([current.version.clone()]);
+    while let Some(from_version) = queue.pop_front() {
+        let edges = MIGRATION_EDGES
+            .prefix((contract_name, &from_version))
+            .range(deps.storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<(String, MigrationEdge)>>>()?;
+        for (to_version, edge) in edges {
+            if steps.contains_key(&to_version) {
+                continue;
+            }
+            if let Some(registration) = NAME_CHAIN_ID_VERSION_TO_REGISTRATION
+                .may_load(deps.storage, (contract_name, &chain_id, &to_version))?
+            {
+                steps.insert(
+                    to_version.clone(),
+                    (from_version.clone(), UpgradeStep { edge, registration }),
+                );
+                queue.push_back(to_version);
+            }
+        }
+    }
+
+    let newest = steps
+        .keys()
+        .filter_map(|version| Some((Version::parse(version).ok()?, version.clone())))
+        .max_by(|(a, _), (b, _)| a.cmp(b))
+        .map(|(_, version)| version);
+    let mut path = vec![];
+    let mut version = newest;
+    while let Some((from_version, step)) = version.and_then(|version| steps.remove(&version)) {
+        path.push(step);
+        version = Some(from_version);
+    }
+    path.reverse();
+
+    to_json_binary(&UpgradePathResponse { current, path })
+}
+
+pub fn query_get_dependencies(deps: Deps, name: String, version: String) -> StdResult<Binary> {
+    let dependencies = DEPENDENCIES
+        .may_load(deps.storage, (&name, &version))?
+        .unwrap_or_default();
+    to_json_binary(&GetDependenciesResponse { dependencies })
+}
+
+pub fn query_resolve_dependencies(
+    deps: Deps,
+    name: String,
+    version: String,
+    chain_id: String,
+) -> StdResult<Binary> {
+    let root = NAME_CHAIN_ID_VERSION_TO_REGISTRATION
+        .load(deps.storage, (&name, &chain_id, &version))
+        .map_err(|_| StdError::GenericErr {
+            msg: ContractError::NotFound {}.to_string(),
+        })?;
+
+    let mut resolved = BTreeMap::new();
+    let pending = dependency_requirements(deps, &root)?;
+    resolved.insert(root.contract_name.clone(), root);
+    let resolved = resolve_dependencies(deps, &chain_id, resolved, pending)?
+        .map_err(|e| StdError::GenericErr { msg: e.to_string() })?;
+
+    to_json_binary(&ResolveDependenciesResponse {
+        registrations: resolved.into_values().collect(),
+    })
+}
+
+/// A dependency still to be resolved: (dependency, who requires it).
+type Requirement = (Dependency, String);
+
+fn dependency_requirements(
+    deps: Deps,
+    registration: &Registration,
+) -> StdResult<VecDeque<Requirement>> {
+    let required_by = format!("{} {}", registration.contract_name, registration.version);
+    Ok(DEPENDENCIES
+        .may_load(
+            deps.storage,
+            (&registration.contract_name, &registration.version),
+        )?
+        .unwrap_or_default()
+        .into_iter()
+        .map(|dependency| (dependency, required_by.clone()))
+        .collect())
+}
+
+/// Resolves pending requirements by backtracking: each contract is tried at
+/// every matching version, newest first, until one leads to a resolution in
+/// which all requirements are met. The outer result fails on storage
+/// errors, the inner one when the requirements can't be met.
+fn resolve_dependencies(
+    deps: Deps,
+    chain_id: &str,
+    resolved: BTreeMap<String, Registration>,
+    mut pending: VecDeque<Requirement>,
+) -> StdResult<Result<BTreeMap<String, Registration>, ContractError>> {
+    let (dependency, required_by) = match pending.pop_front() {
+        Some(requirement) => requirement,
+        None => return Ok(Ok(resolved)),
+    };
+    // Requirements were validated when they were set.
+    let version_req = VersionReq::parse(&dependency.version_req)
+        .map_err(|e| StdError::generic_err(e.to_string()))?;
+
+    if let Some(registration) = resolved.get(&dependency.contract_name) {
+        let matches = Version::parse(&registration.version)
+            .map(|version| version_req.matches(&version))
+            .unwrap_or(false);
+        return if matches {
+            resolve_dependencies(deps, chain_id, resolved, pending)
+        } else {
+            Ok(Err(ContractError::ConflictingDependency {
+                contract_name: dependency.contract_name,
+                version_req: dependency.version_req,
+                required_by,
+                resolved_version: registration.version.clone(),
+            }))
+        };
+    }
+
+    let mut candidates = NAME_CHAIN_ID_VERSION_TO_REGISTRATION
+        .prefix((&dependency.contract_name, chain_id))
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<(String, Registration)>>>()?
+        .into_iter()
+        .filter_map(|(version, registration)| {
+            Version::parse(&version)
+                .ok()
+                .filter(|version| version_req.matches(version))
+                .map(|version| (version, registration))
+        })
+        .collect::<Vec<_>>();
+    candidates.sort_by(|(a, _), (b, _)| b.cmp(a));
+
+    // Report the failure of the newest candidate, which is the one most
+    // likely intended.
+    let mut first_error = None;
+    for (_, registration) in candidates {
+        let mut pending = pending.clone();
+        pending.extend(dependency_requirements(deps, &registration)?);
+        let mut resolved = resolved.clone();
+        resolved.insert(registration.contract_name.clone(), registration);
+        match resolve_dependencies(deps, chain_id, resolved, pending)? {
+            Ok(resolved) => return Ok(Ok(resolved)),
+            Err(e) => {
+                first_error.get_or_insert(e);
+            }
+        }
+    }
+
+    Ok(Err(first_error.unwrap_or(
+        ContractError::UnsatisfiableDependency {
+            contract_name: dependency.contract_name,
+            version_req: dependency.version_req,
+            required_by,
+            chain_id: chain_id.to_string(),
+        },
+    )))
+}
+
+pub fn query_get_interfaces(deps: Deps, name: String, version: String) -> StdResult<Binary> {
+    let interfaces = INTERFACES
+        .may_load(deps.storage, (&name, &version))?
+        .unwrap_or_default();
+    to_json_binary(&GetInterfacesResponse { interfaces })
+}
+
+pub fn query_list_by_interface(
+    deps: Deps,
+    interface: String,
+    chain_id: String,
+    start_after: Option<(String, String)>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+
+    let mut implementations = vec![];
+    for item in INTERFACE_IMPLEMENTATIONS.sub_prefix(&interface).range(
+        deps.storage,
+        start_after
+            .as_ref()
+            .map(|(name, version)| Bound::exclusive((name.as_str(), version.as_str()))),
+        None,
+        Order::Ascending,
+    ) {
+        if implementations.len() == limit {
+            break;
+        }
+        let ((name, version), interface_version) = item?;
+        // Only list versions registered on the chain.
+        if let Some(registration) = NAME_CHAIN_ID_VERSION_TO_REGISTRATION
+            .may_load(deps.storage, (&name, &chain_id, &version))?
+        {
+            implementations.push(InterfaceImplementation {
+                interface: Interface {
+                    name: interface.clone(),
+                    version: interface_version,
+                },
+                registration,
+            });
+        }
+    }
+
+    to_json_binary(&ListByInterfaceResponse { implementations })
+}
+
+pub fn query_get_schemas(deps: Deps, name: String, version: String) -> StdResult<Binary> {
+    let schemas =
+        SCHEMAS
+            .load(deps.storage, (&name, &version))
+            .map_err(|_| StdError::GenericErr {
+                msg: ContractError::NotFound {}.to_string(),
+            })?;
+
+    to_json_binary(&GetSchemasResponse { schemas })
+}
+
+pub fn query_sync_channels(deps: Deps) -> StdResult<Binary> {
+    let trusted_channel = TRUSTED_CHANNEL.may_load(deps.storage)?;
+    let channels = SYNC_CHANNELS
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<String>>>()?;
+
+    let peers = SYNC_PEERS
+        .keys(deps.storage, None, None, Order::Ascending)
+        .map(|item| {
+            item.map(|(connection_id, port_id)| SyncPeer {
+                connection_id,
+                port_id,
+            })
+        })
+        .collect::<StdResult<Vec<SyncPeer>>>()?;
+
+    to_json_binary(&SyncChannelsResponse {
+        trusted_channel,
+        channels,
+        peers,
+    })
+}
+
+pub fn query_list_undelivered_updates(
+    deps: Deps,
+    channel_id: String,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+
+    let updates = UNDELIVERED_UPDATES
+        .prefix(&channel_id)
+        .range(
+            deps.storage,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .map(|item| item.map(|(sequence, update)| SyncPacket { sequence, update }))
+        .collect::<StdResult<Vec<SyncPacket>>>()?;
+
+    to_json_binary(&ListUndeliveredUpdatesResponse { updates })
+}
+
+pub fn query_signers(deps: Deps) -> StdResult<Binary> {
+    to_json_binary(&SignersResponse {
+        signer_set: SIGNER_SET.may_load(deps.storage)?,
+        next_nonce: IMPORT_NONCE.may_load(deps.storage)?.unwrap_or_default(),
+    })
+}
+
+pub fn query_pause_info(deps: Deps, env: Env) -> StdResult<Binary> {
+    let config = CONFIG.load(deps.storage)?;
+    let paused_until = config
+        .paused_until
+        .filter(|until| !until.is_expired(&env.block));
+
+    to_json_binary(&PauseInfoResponse {
+        paused_until,
+        frozen: config.frozen,
+    })
+}
+
+pub fn query_list_delegations(
+    deps: Deps,
+    env: Env,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    let start_after = start_after
+        .map(|address| deps.api.addr_validate(&address))
+        .transpose()?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+
+    let delegations = DELEGATIONS
+        .range(
+            deps.storage,
+            start_after.as_ref().map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .filter(|item| {
+            item.as_ref().map_or(true, |(_, delegation)| {
+                !delegation.expiration.is_expired(&env.block)
+            })
+        })
+        .take(limit)
+        .map(|item| item.map(|(_, delegation)| delegation))
+        .collect::<StdResult<Vec<Delegation>>>()?;
+
+    to_json_binary(&ListDelegationsResponse { delegations })
+}
+
+pub fn query_list_queued_actions(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+
+    let actions = QUEUED_ACTIONS
+        .range(
+            deps.storage,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .map(|item| item.map(|(_, action)| action))
+        .collect::<StdResult<Vec<QueuedAction>>>()?;
+
+    to_json_binary(&ListQueuedActionsResponse { actions })
+}
+
+pub fn query_list_registration_proposals(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+
+    let proposals = REGISTRATION_PROPOSALS
+        .range(
+            deps.storage,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .map(|item| item.map(|(_, proposal)| proposal))
+        .collect::<StdResult<Vec<RegistrationProposal>>>()?;
+
+    to_json_binary(&ListRegistrationProposalsResponse { proposals })
+}
+
+pub fn query_get_chain(deps: Deps, chain_id: String) -> StdResult<Binary> {
+    let chain = CHAINS
+        .load(deps.storage, &chain_id)
+        .map_err(|_| StdError::GenericErr {
+            msg: ContractError::NotFound {}.to_string(),
+        })?;
+
+    to_json_binary(&GetChainResponse { chain })
+}
+
+pub fn query_list_chains(deps: Deps, is_testnet: Option<bool>) -> StdResult<Binary> {
+    let chains = CHAINS
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, chain)| chain))
+        .filter(|item| match (item, is_testnet) {
+            (Ok(chain), Some(is_testnet)) => chain.is_testnet == is_testnet,
+            _ => true,
+        })
+        .collect::<StdResult<Vec<ChainInfo>>>()?;
+    to_json_binary(&ListChainsResponse { chains })
+}
+
+pub fn query_get_namespace(deps: Deps, namespace: String) -> StdResult<Binary> {
+    let namespace =
+        NAMESPACES
+            .load(deps.storage, &namespace)
+            .map_err(|_| StdError::GenericErr {
+                msg: ContractError::NotFound {}.to_string(),
+            })?;
+
+    to_json_binary(&GetNamespaceResponse { namespace })
 }
@@ -1,5 +1,5 @@
-use cosmwasm_std::StdError;
-use cw_utils::PaymentError;
+use cosmwasm_std::{Instantiate2AddressError, OverflowError, StdError, VerificationError};
+use cw_utils::{Expiration, ParseReplyError, PaymentError};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -11,12 +11,102 @@ pub enum ContractError {
     #[error("{0}")]
     Payment(#[from] PaymentError),
 
+    #[error("{0}")]
+    Overflow(#[from] OverflowError),
+
+    #[error("{0}")]
+    ParseReply(#[from] ParseReplyError),
+
+    #[error("{0}")]
+    Instantiate2Address(#[from] Instantiate2AddressError),
+
+    #[error("{0}")]
+    Verification(#[from] VerificationError),
+
     #[error("Unauthorized; only admin may register or unregister code ID")]
     UnauthorizedRegistration {},
 
     #[error("Unauthorized; only admin may update admin")]
     UnauthorizedUpdateAdmin {},
 
+    #[error("Unauthorized; only admin or the contract's instantiator may migrate it")]
+    UnauthorizedMigration {},
+
+    #[error("Unauthorized; only admin may add chains")]
+    UnauthorizedAddChain {},
+
+    #[error("Unauthorized; only admin may register namespaces")]
+    UnauthorizedRegisterNamespace {},
+
+    #[error("Unauthorized; only the namespace owner may update the namespace")]
+    UnauthorizedUpdateNamespace {},
+
+    #[error("Unauthorized; only admin may set the trusted channel")]
+    UnauthorizedSetTrustedChannel {},
+
+    #[error("Unauthorized; only admin may manage sync peers and channels")]
+    UnauthorizedSyncPeer {},
+
+    #[error("Unauthorized; only admin may resync channels")]
+    UnauthorizedResync {},
+
+    #[error("Unauthorized; only admin may set the registry signers")]
+    UnauthorizedSetSigners {},
+
+    #[error("Unauthorized; only admin or guardian may pause, and only admin may unpause or freeze the registry")]
+    UnauthorizedPause {},
+
+    #[error("Unauthorized; only admin may set the timelock delay")]
+    UnauthorizedSetTimelockDelay {},
+
+    #[error("Unauthorized; only admin or guardian may cancel queued actions")]
+    UnauthorizedCancel {},
+
+    #[error("Unauthorized; only admin may cancel guardian changes")]
+    UnauthorizedCancelSetGuardian {},
+
+    #[error("Unauthorized; only admin may set the guardian")]
+    UnauthorizedSetGuardian {},
+
+    #[error("Unauthorized; only admin may delegate release rights")]
+    UnauthorizedDelegate {},
+
+    #[error("Only the namespace owner may publish {0}, so it can't be delegated")]
+    NamespacedDelegation(String),
+
+    #[error("Period must be at least one block")]
+    InvalidPeriod {},
+
+    #[error("Delegation used all its registrations this period; retry at height {0}")]
+    DelegationExhausted(u64),
+
+    #[error("Unauthorized; only admin may update the config")]
+    UnauthorizedUpdateConfig {},
+
+    #[error("Unauthorized; only admin may approve or reject registrations")]
+    UnauthorizedApproveRegistration {},
+
+    #[error("Maximum name length must be between 1 and {0}")]
+    InvalidMaxNameLength(usize),
+
+    #[error("Prerelease version {0} may not be registered")]
+    PrereleaseVersion(String),
+
+    #[error("Rate limited; retry at height {retry_at}")]
+    RateLimited { retry_at: u64 },
+
+    #[error("Queued action {id} may not be executed before height {executable_at}")]
+    ActionNotReady { id: u64, executable_at: u64 },
+
+    #[error("Registry is paused until {0}")]
+    Paused(Expiration),
+
+    #[error("Registry is paused until {0}; the guardian may only extend the pause")]
+    PauseNotExtended(Expiration),
+
+    #[error("Registry is frozen and read-only")]
+    Frozen {},
+
     #[error("Incorrect payment amount")]
     IncorrectPaymentAmount {},
 
@@ -26,6 +116,137 @@ pub enum ContractError {
     #[error("Code ID {0} has already been registered on chain {1}")]
     CodeIDAlreadyRegistered(u64, String),
 
+    #[error("Chain {0} is not in the chain catalog")]
+    UnknownChain(String),
+
+    #[error("Chain {0} has already been added")]
+    ChainAlreadyExists(String),
+
+    #[error(
+        "Invalid chain ID {0:?}; must be non-empty and contain no whitespace or control characters"
+    )]
+    InvalidChainId(String),
+
+    #[error("Invalid bech32 prefix {0:?}; must be non-empty lowercase alphanumeric")]
+    InvalidBech32Prefix(String),
+
+    #[error("Contract name may not be empty")]
+    EmptyContractName {},
+
+    #[error(
+        "Contract name {0:?} may contain at most one '/' separating a non-empty namespace and name"
+    )]
+    InvalidContractNamespace(String),
+
+    #[error("Contract name {0:?} has a segment longer than {1} characters")]
+    ContractNameTooLong(String, usize),
+
+    #[error("Contract name {0:?} contains invalid character {1:?}; only a-z, 0-9, '-' and '_' are allowed")]
+    InvalidContractNameChar(String, char),
+
+    #[error("Namespace {0} has not been registered")]
+    UnknownNamespace(String),
+
+    #[error("Namespace {0} has already been registered")]
+    NamespaceAlreadyRegistered(String),
+
+    #[error("Version {0:?} is not valid semver: {1}")]
+    InvalidVersion(String, String),
+
+    #[error("Version requirement {0:?} is not valid semver: {1}")]
+    InvalidVersionReq(String, String),
+
+    #[error("Checksum {0:?} is not valid hex")]
+    InvalidChecksum(String),
+
+    #[error("Migration edge must go from an older version to a newer one, not {0} to {1}")]
+    InvalidMigrationEdge(String, String),
+
+    #[error("Contract {0} is running code ID {1}, which is not registered")]
+    UnregisteredContractCode(String, u64),
+
+    #[error("Contract {0} is a {1} contract and may not be migrated to {2}")]
+    MigrationNameMismatch(String, String, String),
+
+    #[error("Contract {0} is at version {1} and may only be migrated to a newer version, not {2}")]
+    MigrationNotNewer(String, String, String),
+
+    #[error("The registry is not the admin of contract {0}")]
+    RegistryNotContractAdmin(String),
+
+    #[error("No version of {contract_name} matching {version_req} (required by {required_by}) is registered on chain {chain_id}")]
+    UnsatisfiableDependency {
+        contract_name: String,
+        version_req: String,
+        required_by: String,
+        chain_id: String,
+    },
+
+    #[error("{contract_name} {version_req} (required by {required_by}) conflicts with {contract_name} {resolved_version} required elsewhere")]
+    ConflictingDependency {
+        contract_name: String,
+        version_req: String,
+        required_by: String,
+        resolved_version: String,
+    },
+
+    #[error("Schema hash {0:?} is not a hex encoded SHA-256 hash")]
+    InvalidSchemaHash(String),
+
+    #[error("Only unordered channels are supported")]
+    OrderedChannel {},
+
+    #[error("Invalid IBC channel version {0:?}, expected {1:?}")]
+    InvalidIbcVersion(String, String),
+
+    #[error("Port {1} on connection {0} is not an approved sync peer")]
+    UnapprovedSyncPeer(String, String),
+
+    #[error("Unknown IBC channel {0}")]
+    UnknownChannel(String),
+
+    #[error("Channel {0} is not trusted to update registrations")]
+    UntrustedChannel(String),
+
+    #[error("Expected update {expected} from the trusted channel, got {sequence}")]
+    UnexpectedSequence { expected: u64, sequence: u64 },
+
+    #[error("Registry follows channel {0} and only accepts registration updates from it")]
+    FollowerRegistry(String),
+
+    #[error("Threshold {0} must be between 1 and the number of signers, {1}")]
+    InvalidSignerThreshold(u32, usize),
+
+    #[error("Signer {0} is listed more than once")]
+    DuplicateSigner(String),
+
+    #[error("No registry signers are configured")]
+    NoSigners {},
+
+    #[error("Payload is for registry {registry} on chain {chain_id}")]
+    WrongRegistry { chain_id: String, registry: String },
+
+    #[error("Expected payload nonce {expected}, got {nonce}")]
+    InvalidNonce { expected: u64, nonce: u64 },
+
+    #[error("{0} is not a registry signer")]
+    UnknownSigner(String),
+
+    #[error("Invalid signature from signer {0}")]
+    InvalidSignature(String),
+
+    #[error("Payload is signed by {0} signers, {1} required")]
+    InsufficientSignatures(usize, u32),
+
+    #[error("Unknown reply ID {0}")]
+    UnknownReplyId(u64),
+
+    #[error("Batch entry {index} failed: {error}")]
+    BatchEntry {
+        index: usize,
+        error: Box<ContractError>,
+    },
+
     #[error("Invalid CW20, this address is not a CW20")]
     InvalidCw20 {},
 
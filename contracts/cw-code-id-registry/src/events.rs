@@ -0,0 +1,43 @@
+use cosmwasm_std::{Addr, Event};
+
+use crate::state::Registration;
+
+/// Version of the events below. Bumped whenever an event's type or
+/// attributes change, so indexers know how to read them.
+pub const EVENT_VERSION: &str = "1";
+
+pub const REGISTER_EVENT: &str = "code_id_registry/register";
+pub const UNREGISTER_EVENT: &str = "code_id_registry/unregister";
+pub const UPDATE_ADMIN_EVENT: &str = "code_id_registry/update_admin";
+
+/// Every field of a registration, so the registry can be rebuilt from
+/// register and unregister events alone. Empty checksums are omitted, as
+/// attribute values may not be empty.
+fn registration_event(ty: &str, chain_id: &str, registration: &Registration) -> Event {
+    let event = Event::new(ty)
+        .add_attribute("event_version", EVENT_VERSION)
+        .add_attribute("chain_id", chain_id)
+        .add_attribute("contract_name", &registration.contract_name)
+        .add_attribute("version", &registration.version)
+        .add_attribute("code_id", registration.code_id.to_string());
+    if registration.checksum.is_empty() {
+        event
+    } else {
+        event.add_attribute("checksum", &registration.checksum)
+    }
+}
+
+pub fn register_event(chain_id: &str, registration: &Registration) -> Event {
+    registration_event(REGISTER_EVENT, chain_id, registration)
+}
+
+pub fn unregister_event(chain_id: &str, registration: &Registration) -> Event {
+    registration_event(UNREGISTER_EVENT, chain_id, registration)
+}
+
+pub fn update_admin_event(old_admin: &Addr, new_admin: &Addr) -> Event {
+    Event::new(UPDATE_ADMIN_EVENT)
+        .add_attribute("event_version", EVENT_VERSION)
+        .add_attribute("old_admin", old_admin)
+        .add_attribute("new_admin", new_admin)
+}
@@ -0,0 +1,785 @@
+use cosmwasm_std::{
+    to_json_binary, Addr, Binary, Coin, CustomQuery, QuerierWrapper, StdResult, WasmMsg,
+};
+use cw_utils::Expiration;
+use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::msg::{
+    ExecuteMsg, GetChainResponse, GetDependenciesResponse, GetInterfacesResponse,
+    GetNamespaceResponse, GetRegistrationResponse, GetRegistrationsResponse, GetSchemasResponse,
+    ListByInterfaceResponse, ListChainsResponse, ListDelegationsResponse, ListInstancesResponse,
+    ListQueuedActionsResponse, ListRegistrationProposalsResponse, ListRegistrationsResponse,
+    ListUndeliveredUpdatesResponse, PauseInfoResponse, PayloadSignature, PredictAddressResponse,
+    QueryMsg, RateLimitUpdate, RegisterMsg, RegistrationRequest, ResolveDependenciesResponse,
+    SignersResponse, SyncChannelsResponse, UncheckedGuardian, UnregisterMsg, UpgradePathResponse,
+};
+use crate::state::{
+    Config, Dependency, Interface, MessageSchemas, Registration, RegistrationMode, Signer,
+    ValidationRules,
+};
+
+/// CodeIdRegistryContract is a wrapper around Addr that provides helpers
+/// for contracts that depend on the registry. Execute helpers build the
+/// WasmMsg to send, and query helpers query the registry through a
+/// QuerierWrapper.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct CodeIdRegistryContract(pub Addr);
+
+impl CodeIdRegistryContract {
+    pub fn new(addr: Addr) -> Self {
+        CodeIdRegistryContract(addr)
+    }
+
+    pub fn addr(&self) -> Addr {
+        self.0.clone()
+    }
+
+    pub fn encode_msg(&self, msg: ExecuteMsg) -> StdResult<WasmMsg> {
+        self.encode_msg_with_funds(msg, vec![])
+    }
+
+    pub fn encode_msg_with_funds(&self, msg: ExecuteMsg, funds: Vec<Coin>) -> StdResult<WasmMsg> {
+        Ok(WasmMsg::Execute {
+            contract_addr: self.addr().into(),
+            msg: to_json_binary(&msg)?,
+            funds,
+        })
+    }
+
+    /// funds must be the registration fee, or empty if there is none or the
+    /// sender is the admin.
+    pub fn register(&self, msg: RegisterMsg, funds: Vec<Coin>) -> StdResult<WasmMsg> {
+        let RegisterMsg {
+            contract_name,
+            version,
+            chain_id,
+            code_id,
+            checksum,
+        } = msg;
+        self.encode_msg_with_funds(
+            ExecuteMsg::Register {
+                contract_name,
+                version,
+                chain_id,
+                code_id,
+                checksum,
+            },
+            funds,
+        )
+    }
+
+    pub fn unregister(&self, msg: UnregisterMsg) -> StdResult<WasmMsg> {
+        let UnregisterMsg {
+            contract_name,
+            chain_id,
+            code_id,
+            version,
+        } = msg;
+        self.encode_msg(ExecuteMsg::Unregister {
+            contract_name,
+            chain_id,
+            code_id,
+            version,
+        })
+    }
+
+    /// funds must be the registration fee for every registration, or empty
+    /// if there is none or the sender is the admin.
+    pub fn register_batch(
+        &self,
+        registrations: Vec<RegisterMsg>,
+        funds: Vec<Coin>,
+    ) -> StdResult<WasmMsg> {
+        self.encode_msg_with_funds(ExecuteMsg::RegisterBatch { registrations }, funds)
+    }
+
+    pub fn unregister_batch(&self, registrations: Vec<UnregisterMsg>) -> StdResult<WasmMsg> {
+        self.encode_msg(ExecuteMsg::UnregisterBatch { registrations })
+    }
+
+    /// The funds sent are passed on to the instantiated contract, so must be
+    /// exactly funds.
+    pub fn instantiate_by_name(
+        &self,
+        name: impl Into<String>,
+        version_req: Option<String>,
+        msg: Binary,
+        label: impl Into<String>,
+        admin: Option<String>,
+        funds: Vec<Coin>,
+    ) -> StdResult<WasmMsg> {
+        self.encode_msg_with_funds(
+            ExecuteMsg::InstantiateByName {
+                name: name.into(),
+                version_req,
+                msg,
+                label: label.into(),
+                admin,
+                funds: funds.clone(),
+            },
+            funds,
+        )
+    }
+
+    /// The funds sent are passed on to the instantiated contract, so must be
+    /// exactly funds.
+    #[allow(clippy::too_many_arguments)]
+    pub fn instantiate2_by_name(
+        &self,
+        name: impl Into<String>,
+        version: impl Into<String>,
+        msg: Binary,
+        label: impl Into<String>,
+        admin: Option<String>,
+        funds: Vec<Coin>,
+        salt: Binary,
+    ) -> StdResult<WasmMsg> {
+        self.encode_msg_with_funds(
+            ExecuteMsg::Instantiate2ByName {
+                name: name.into(),
+                version: version.into(),
+                msg,
+                label: label.into(),
+                admin,
+                funds: funds.clone(),
+                salt,
+            },
+            funds,
+        )
+    }
+
+    pub fn report_instance(&self, address: impl Into<String>) -> StdResult<WasmMsg> {
+        self.encode_msg(ExecuteMsg::ReportInstance {
+            address: address.into(),
+        })
+    }
+
+    pub fn add_migration_edge(
+        &self,
+        contract_name: impl Into<String>,
+        from_version: impl Into<String>,
+        to_version: impl Into<String>,
+        migrate_msg: Option<String>,
+        breaking: bool,
+    ) -> StdResult<WasmMsg> {
+        self.encode_msg(ExecuteMsg::AddMigrationEdge {
+            contract_name: contract_name.into(),
+            from_version: from_version.into(),
+            to_version: to_version.into(),
+            migrate_msg,
+            breaking,
+        })
+    }
+
+    pub fn remove_migration_edge(
+        &self,
+        contract_name: impl Into<String>,
+        from_version: impl Into<String>,
+        to_version: impl Into<String>,
+    ) -> StdResult<WasmMsg> {
+        self.encode_msg(ExecuteMsg::RemoveMigrationEdge {
+            contract_name: contract_name.into(),
+            from_version: from_version.into(),
+            to_version: to_version.into(),
+        })
+    }
+
+    pub fn migrate_to_version(
+        &self,
+        contract_addr: impl Into<String>,
+        name: impl Into<String>,
+        version: impl Into<String>,
+        msg: Binary,
+    ) -> StdResult<WasmMsg> {
+        self.encode_msg(ExecuteMsg::MigrateToVersion {
+            contract_addr: contract_addr.into(),
+            name: name.into(),
+            version: version.into(),
+            msg,
+        })
+    }
+
+    pub fn set_dependencies(
+        &self,
+        contract_name: impl Into<String>,
+        version: impl Into<String>,
+        dependencies: Vec<Dependency>,
+    ) -> StdResult<WasmMsg> {
+        self.encode_msg(ExecuteMsg::SetDependencies {
+            contract_name: contract_name.into(),
+            version: version.into(),
+            dependencies,
+        })
+    }
+
+    pub fn set_interfaces(
+        &self,
+        contract_name: impl Into<String>,
+        version: impl Into<String>,
+        interfaces: Vec<Interface>,
+    ) -> StdResult<WasmMsg> {
+        self.encode_msg(ExecuteMsg::SetInterfaces {
+            contract_name: contract_name.into(),
+            version: version.into(),
+            interfaces,
+        })
+    }
+
+    pub fn set_schemas(
+        &self,
+        contract_name: impl Into<String>,
+        version: impl Into<String>,
+        schemas: MessageSchemas,
+    ) -> StdResult<WasmMsg> {
+        self.encode_msg(ExecuteMsg::SetSchemas {
+            contract_name: contract_name.into(),
+            version: version.into(),
+            schemas,
+        })
+    }
+
+    pub fn set_trusted_channel(&self, channel_id: Option<String>) -> StdResult<WasmMsg> {
+        self.encode_msg(ExecuteMsg::SetTrustedChannel { channel_id })
+    }
+
+    pub fn add_sync_peer(
+        &self,
+        connection_id: impl Into<String>,
+        port_id: impl Into<String>,
+    ) -> StdResult<WasmMsg> {
+        self.encode_msg(ExecuteMsg::AddSyncPeer {
+            connection_id: connection_id.into(),
+            port_id: port_id.into(),
+        })
+    }
+
+    pub fn remove_sync_peer(
+        &self,
+        connection_id: impl Into<String>,
+        port_id: impl Into<String>,
+    ) -> StdResult<WasmMsg> {
+        self.encode_msg(ExecuteMsg::RemoveSyncPeer {
+            connection_id: connection_id.into(),
+            port_id: port_id.into(),
+        })
+    }
+
+    pub fn close_sync_channel(&self, channel_id: impl Into<String>) -> StdResult<WasmMsg> {
+        self.encode_msg(ExecuteMsg::CloseSyncChannel {
+            channel_id: channel_id.into(),
+        })
+    }
+
+    pub fn resync(&self, channel_id: impl Into<String>) -> StdResult<WasmMsg> {
+        self.encode_msg(ExecuteMsg::Resync {
+            channel_id: channel_id.into(),
+        })
+    }
+
+    pub fn import_signed(
+        &self,
+        payload: Binary,
+        signatures: Vec<PayloadSignature>,
+    ) -> StdResult<WasmMsg> {
+        self.encode_msg(ExecuteMsg::ImportSigned {
+            payload,
+            signatures,
+        })
+    }
+
+    pub fn set_signers(&self, signers: Vec<Signer>, threshold: u32) -> StdResult<WasmMsg> {
+        self.encode_msg(ExecuteMsg::SetSigners { signers, threshold })
+    }
+
+    pub fn pause(&self, until: Option<Expiration>) -> StdResult<WasmMsg> {
+        self.encode_msg(ExecuteMsg::Pause { until })
+    }
+
+    pub fn unpause(&self) -> StdResult<WasmMsg> {
+        self.encode_msg(ExecuteMsg::Unpause {})
+    }
+
+    pub fn freeze(&self) -> StdResult<WasmMsg> {
+        self.encode_msg(ExecuteMsg::Freeze {})
+    }
+
+    pub fn set_timelock_delay(&self, blocks: u64) -> StdResult<WasmMsg> {
+        self.encode_msg(ExecuteMsg::SetTimelockDelay { blocks })
+    }
+
+    pub fn execute_queued(&self, id: u64) -> StdResult<WasmMsg> {
+        self.encode_msg(ExecuteMsg::ExecuteQueued { id })
+    }
+
+    pub fn cancel_queued(&self, id: u64) -> StdResult<WasmMsg> {
+        self.encode_msg(ExecuteMsg::CancelQueued { id })
+    }
+
+    pub fn set_guardian(&self, guardian: Option<UncheckedGuardian>) -> StdResult<WasmMsg> {
+        self.encode_msg(ExecuteMsg::SetGuardian { guardian })
+    }
+
+    pub fn delegate(
+        &self,
+        delegate: impl Into<String>,
+        names: Vec<String>,
+        max_registrations: u64,
+        period: u64,
+        expiration: Option<Expiration>,
+    ) -> StdResult<WasmMsg> {
+        self.encode_msg(ExecuteMsg::Delegate {
+            delegate: delegate.into(),
+            names,
+            max_registrations,
+            period,
+            expiration,
+        })
+    }
+
+    pub fn revoke_delegation(&self, delegate: impl Into<String>) -> StdResult<WasmMsg> {
+        self.encode_msg(ExecuteMsg::RevokeDelegation {
+            delegate: delegate.into(),
+        })
+    }
+
+    pub fn update_config(
+        &self,
+        registration_mode: Option<RegistrationMode>,
+        validation: Option<ValidationRules>,
+        registration_fee: Option<Vec<Coin>>,
+        rate_limit: Option<RateLimitUpdate>,
+    ) -> StdResult<WasmMsg> {
+        self.encode_msg(ExecuteMsg::UpdateConfig {
+            registration_mode,
+            validation,
+            registration_fee,
+            rate_limit,
+        })
+    }
+
+    pub fn approve_registration(&self, id: u64) -> StdResult<WasmMsg> {
+        self.encode_msg(ExecuteMsg::ApproveRegistration { id })
+    }
+
+    pub fn reject_registration(&self, id: u64) -> StdResult<WasmMsg> {
+        self.encode_msg(ExecuteMsg::RejectRegistration { id })
+    }
+
+    pub fn update_admin(&self, admin: impl Into<String>) -> StdResult<WasmMsg> {
+        self.encode_msg(ExecuteMsg::UpdateAdmin {
+            admin: admin.into(),
+        })
+    }
+
+    pub fn add_chain(
+        &self,
+        chain_id: impl Into<String>,
+        bech32_prefix: impl Into<String>,
+        pretty_name: impl Into<String>,
+        is_testnet: bool,
+    ) -> StdResult<WasmMsg> {
+        self.encode_msg(ExecuteMsg::AddChain {
+            chain_id: chain_id.into(),
+            bech32_prefix: bech32_prefix.into(),
+            pretty_name: pretty_name.into(),
+            is_testnet,
+        })
+    }
+
+    pub fn register_namespace(
+        &self,
+        namespace: impl Into<String>,
+        owner: impl Into<String>,
+    ) -> StdResult<WasmMsg> {
+        self.encode_msg(ExecuteMsg::RegisterNamespace {
+            namespace: namespace.into(),
+            owner: owner.into(),
+        })
+    }
+
+    pub fn update_namespace_owner(
+        &self,
+        namespace: impl Into<String>,
+        owner: impl Into<String>,
+    ) -> StdResult<WasmMsg> {
+        self.encode_msg(ExecuteMsg::UpdateNamespaceOwner {
+            namespace: namespace.into(),
+            owner: owner.into(),
+        })
+    }
+
+    pub fn add_namespace_delegate(
+        &self,
+        namespace: impl Into<String>,
+        delegate: impl Into<String>,
+    ) -> StdResult<WasmMsg> {
+        self.encode_msg(ExecuteMsg::AddNamespaceDelegate {
+            namespace: namespace.into(),
+            delegate: delegate.into(),
+        })
+    }
+
+    pub fn remove_namespace_delegate(
+        &self,
+        namespace: impl Into<String>,
+        delegate: impl Into<String>,
+    ) -> StdResult<WasmMsg> {
+        self.encode_msg(ExecuteMsg::RemoveNamespaceDelegate {
+            namespace: namespace.into(),
+            delegate: delegate.into(),
+        })
+    }
+
+    fn query<T: DeserializeOwned, Q: CustomQuery>(
+        &self,
+        querier: &QuerierWrapper<Q>,
+        msg: &QueryMsg,
+    ) -> StdResult<T> {
+        querier.query_wasm_smart(self.addr(), msg)
+    }
+
+    pub fn admin<Q: CustomQuery>(&self, querier: &QuerierWrapper<Q>) -> StdResult<Addr> {
+        self.query(querier, &QueryMsg::Admin {})
+    }
+
+    pub fn config<Q: CustomQuery>(&self, querier: &QuerierWrapper<Q>) -> StdResult<Config> {
+        self.query(querier, &QueryMsg::Config {})
+    }
+
+    /// Returns None rather than an error if the registration is not found.
+    /// If version is None, returns the latest version registered.
+    pub fn get_registration<Q: CustomQuery>(
+        &self,
+        querier: &QuerierWrapper<Q>,
+        name: impl Into<String>,
+        chain_id: impl Into<String>,
+        version: Option<String>,
+    ) -> StdResult<Option<Registration>> {
+        let request = RegistrationRequest {
+            name: name.into(),
+            chain_id: chain_id.into(),
+            version,
+        };
+        let response: GetRegistrationsResponse = self.query(
+            querier,
+            &QueryMsg::GetRegistrations {
+                requests: vec![request],
+            },
+        )?;
+        Ok(response.registrations.into_iter().flatten().next())
+    }
+
+    /// Returns None rather than an error if the code ID is not registered.
+    pub fn code_id_info<Q: CustomQuery>(
+        &self,
+        querier: &QuerierWrapper<Q>,
+        chain_id: impl Into<String>,
+        code_id: u64,
+    ) -> StdResult<Option<Registration>> {
+        let response: GetRegistrationsResponse = self.query(
+            querier,
+            &QueryMsg::GetCodeIdInfos {
+                chain_id: chain_id.into(),
+                code_ids: vec![code_id],
+            },
+        )?;
+        Ok(response.registrations.into_iter().flatten().next())
+    }
+
+    pub fn get_registrations<Q: CustomQuery>(
+        &self,
+        querier: &QuerierWrapper<Q>,
+        requests: Vec<RegistrationRequest>,
+    ) -> StdResult<Vec<Option<Registration>>> {
+        let response: GetRegistrationsResponse =
+            self.query(querier, &QueryMsg::GetRegistrations { requests })?;
+        Ok(response.registrations)
+    }
+
+    pub fn code_id_infos<Q: CustomQuery>(
+        &self,
+        querier: &QuerierWrapper<Q>,
+        chain_id: impl Into<String>,
+        code_ids: Vec<u64>,
+    ) -> StdResult<Vec<Option<Registration>>> {
+        let response: GetRegistrationsResponse = self.query(
+            querier,
+            &QueryMsg::GetCodeIdInfos {
+                chain_id: chain_id.into(),
+                code_ids,
+            },
+        )?;
+        Ok(response.registrations)
+    }
+
+    /// Picks a registered version of every contract in the dependency tree
+    /// of name at version.
+    pub fn resolve<Q: CustomQuery>(
+        &self,
+        querier: &QuerierWrapper<Q>,
+        name: impl Into<String>,
+        version: impl Into<String>,
+        chain_id: impl Into<String>,
+    ) -> StdResult<Vec<Registration>> {
+        let response: ResolveDependenciesResponse = self.query(
+            querier,
+            &QueryMsg::ResolveDependencies {
+                name: name.into(),
+                version: version.into(),
+                chain_id: chain_id.into(),
+            },
+        )?;
+        Ok(response.registrations)
+    }
+
+    pub fn list_registrations<Q: CustomQuery>(
+        &self,
+        querier: &QuerierWrapper<Q>,
+        name: impl Into<String>,
+        chain_id: impl Into<String>,
+    ) -> StdResult<ListRegistrationsResponse> {
+        self.query(
+            querier,
+            &QueryMsg::ListRegistrations {
+                name: name.into(),
+                chain_id: chain_id.into(),
+            },
+        )
+    }
+
+    pub fn predict_address<Q: CustomQuery>(
+        &self,
+        querier: &QuerierWrapper<Q>,
+        name: impl Into<String>,
+        version: impl Into<String>,
+        creator: impl Into<String>,
+        salt: Binary,
+    ) -> StdResult<PredictAddressResponse> {
+        self.query(
+            querier,
+            &QueryMsg::PredictAddress {
+                name: name.into(),
+                version: version.into(),
+                creator: creator.into(),
+                salt,
+            },
+        )
+    }
+
+    pub fn list_instances<Q: CustomQuery>(
+        &self,
+        querier: &QuerierWrapper<Q>,
+        chain_id: impl Into<String>,
+        code_id: u64,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<ListInstancesResponse> {
+        self.query(
+            querier,
+            &QueryMsg::ListInstances {
+                chain_id: chain_id.into(),
+                code_id,
+                start_after,
+                limit,
+            },
+        )
+    }
+
+    /// The registration of the code the contract at address is running.
+    pub fn instance_registration<Q: CustomQuery>(
+        &self,
+        querier: &QuerierWrapper<Q>,
+        address: impl Into<String>,
+    ) -> StdResult<Registration> {
+        let response: GetRegistrationResponse = self.query(
+            querier,
+            &QueryMsg::GetInstanceRegistration {
+                address: address.into(),
+            },
+        )?;
+        Ok(response.registration)
+    }
+
+    pub fn upgrade_path<Q: CustomQuery>(
+        &self,
+        querier: &QuerierWrapper<Q>,
+        chain_id: impl Into<String>,
+        code_id: u64,
+    ) -> StdResult<UpgradePathResponse> {
+        self.query(
+            querier,
+            &QueryMsg::UpgradePath {
+                chain_id: chain_id.into(),
+                code_id,
+            },
+        )
+    }
+
+    pub fn dependencies<Q: CustomQuery>(
+        &self,
+        querier: &QuerierWrapper<Q>,
+        name: impl Into<String>,
+        version: impl Into<String>,
+    ) -> StdResult<GetDependenciesResponse> {
+        self.query(
+            querier,
+            &QueryMsg::GetDependencies {
+                name: name.into(),
+                version: version.into(),
+            },
+        )
+    }
+
+    pub fn interfaces<Q: CustomQuery>(
+        &self,
+        querier: &QuerierWrapper<Q>,
+        name: impl Into<String>,
+        version: impl Into<String>,
+    ) -> StdResult<GetInterfacesResponse> {
+        self.query(
+            querier,
+            &QueryMsg::GetInterfaces {
+                name: name.into(),
+                version: version.into(),
+            },
+        )
+    }
+
+    pub fn list_by_interface<Q: CustomQuery>(
+        &self,
+        querier: &QuerierWrapper<Q>,
+        interface: impl Into<String>,
+        chain_id: impl Into<String>,
+        start_after: Option<(String, String)>,
+        limit: Option<u32>,
+    ) -> StdResult<ListByInterfaceResponse> {
+        self.query(
+            querier,
+            &QueryMsg::ListByInterface {
+                interface: interface.into(),
+                chain_id: chain_id.into(),
+                start_after,
+                limit,
+            },
+        )
+    }
+
+    pub fn schemas<Q: CustomQuery>(
+        &self,
+        querier: &QuerierWrapper<Q>,
+        name: impl Into<String>,
+        version: impl Into<String>,
+    ) -> StdResult<GetSchemasResponse> {
+        self.query(
+            querier,
+            &QueryMsg::GetSchemas {
+                name: name.into(),
+                version: version.into(),
+            },
+        )
+    }
+
+    pub fn sync_channels<Q: CustomQuery>(
+        &self,
+        querier: &QuerierWrapper<Q>,
+    ) -> StdResult<SyncChannelsResponse> {
+        self.query(querier, &QueryMsg::SyncChannels {})
+    }
+
+    pub fn list_undelivered_updates<Q: CustomQuery>(
+        &self,
+        querier: &QuerierWrapper<Q>,
+        channel_id: impl Into<String>,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<ListUndeliveredUpdatesResponse> {
+        self.query(
+            querier,
+            &QueryMsg::ListUndeliveredUpdates {
+                channel_id: channel_id.into(),
+                start_after,
+                limit,
+            },
+        )
+    }
+
+    pub fn signers<Q: CustomQuery>(
+        &self,
+        querier: &QuerierWrapper<Q>,
+    ) -> StdResult<SignersResponse> {
+        self.query(querier, &QueryMsg::Signers {})
+    }
+
+    pub fn pause_info<Q: CustomQuery>(
+        &self,
+        querier: &QuerierWrapper<Q>,
+    ) -> StdResult<PauseInfoResponse> {
+        self.query(querier, &QueryMsg::PauseInfo {})
+    }
+
+    pub fn list_registration_proposals<Q: CustomQuery>(
+        &self,
+        querier: &QuerierWrapper<Q>,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<ListRegistrationProposalsResponse> {
+        self.query(
+            querier,
+            &QueryMsg::ListRegistrationProposals { start_after, limit },
+        )
+    }
+
+    pub fn list_delegations<Q: CustomQuery>(
+        &self,
+        querier: &QuerierWrapper<Q>,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<ListDelegationsResponse> {
+        self.query(querier, &QueryMsg::ListDelegations { start_after, limit })
+    }
+
+    pub fn list_queued_actions<Q: CustomQuery>(
+        &self,
+        querier: &QuerierWrapper<Q>,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<ListQueuedActionsResponse> {
+        self.query(querier, &QueryMsg::ListQueuedActions { start_after, limit })
+    }
+
+    pub fn chain<Q: CustomQuery>(
+        &self,
+        querier: &QuerierWrapper<Q>,
+        chain_id: impl Into<String>,
+    ) -> StdResult<GetChainResponse> {
+        self.query(
+            querier,
+            &QueryMsg::GetChain {
+                chain_id: chain_id.into(),
+            },
+        )
+    }
+
+    pub fn list_chains<Q: CustomQuery>(
+        &self,
+        querier: &QuerierWrapper<Q>,
+        is_testnet: Option<bool>,
+    ) -> StdResult<ListChainsResponse> {
+        self.query(querier, &QueryMsg::ListChains { is_testnet })
+    }
+
+    pub fn namespace<Q: CustomQuery>(
+        &self,
+        querier: &QuerierWrapper<Q>,
+        namespace: impl Into<String>,
+    ) -> StdResult<GetNamespaceResponse> {
+        self.query(
+            querier,
+            &QueryMsg::GetNamespace {
+                namespace: namespace.into(),
+            },
+        )
+    }
+}
@@ -0,0 +1,229 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    from_json, to_json_binary, Attribute, Binary, Deps, DepsMut, Env, Event, IbcBasicResponse,
+    IbcChannel, IbcChannelCloseMsg, IbcChannelConnectMsg, IbcChannelOpenMsg,
+    IbcChannelOpenResponse, IbcMsg, IbcOrder, IbcPacket, IbcPacketAckMsg, IbcPacketReceiveMsg,
+    IbcPacketTimeoutMsg, IbcReceiveResponse, IbcTimeout, Order, StdResult, Storage,
+};
+
+use crate::contract::{apply_update, assert_writable};
+use crate::error::ContractError;
+use crate::msg::{RegistryAck, RegistryPacket, SyncPacket};
+use crate::state::{
+    RECEIVED_SEQUENCES, SYNC_CHANNELS, SYNC_PEERS, TRUSTED_CHANNEL, UNDELIVERED_UPDATES,
+};
+
+pub const IBC_VERSION: &str = "cw-code-id-registry-1";
+
+/// Packets not relayed within this many seconds time out.
+const PACKET_LIFETIME: u64 = 60 * 60;
+
+/// Registries on both ends of a channel must speak the same version over an
+/// unordered channel, and the other end must be an approved sync peer.
+fn validate_channel(
+    deps: Deps,
+    channel: &IbcChannel,
+    counterparty_version: Option<&str>,
+) -> Result<(), ContractError> {
+    let connection_id = &channel.connection_id;
+    let port_id = &channel.counterparty_endpoint.port_id;
+    if !SYNC_PEERS.has(deps.storage, (connection_id, port_id)) {
+        return Err(ContractError::UnapprovedSyncPeer(
+            connection_id.clone(),
+            port_id.clone(),
+        ));
+    }
+    if channel.order != IbcOrder::Unordered {
+        return Err(ContractError::OrderedChannel {});
+    }
+    for version in std::iter::once(channel.version.as_str()).chain(counterparty_version) {
+        if version != IBC_VERSION {
+            return Err(ContractError::InvalidIbcVersion(
+                version.to_string(),
+                IBC_VERSION.to_string(),
+            ));
+        }
+    }
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_channel_open(
+    deps: DepsMut,
+    _env: Env,
+    msg: IbcChannelOpenMsg,
+) -> Result<IbcChannelOpenResponse, ContractError> {
+    validate_channel(deps.as_ref(), msg.channel(), msg.counterparty_version())?;
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_channel_connect(
+    deps: DepsMut,
+    _env: Env,
+    msg: IbcChannelConnectMsg,
+) -> Result<IbcBasicResponse, ContractError> {
+    validate_channel(deps.as_ref(), msg.channel(), msg.counterparty_version())?;
+
+    let channel_id = &msg.channel().endpoint.channel_id;
+    SYNC_CHANNELS.save(deps.storage, channel_id, &0)?;
+
+    Ok(IbcBasicResponse::new()
+        .add_attribute("action", "ibc_channel_connect")
+        .add_attribute("channel_id", channel_id))
+}
+
+/// Closing the trusted channel leaves a follower a follower, so it does not
+/// silently start accepting local updates.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_channel_close(
+    deps: DepsMut,
+    _env: Env,
+    msg: IbcChannelCloseMsg,
+) -> Result<IbcBasicResponse, ContractError> {
+    let channel_id = &msg.channel().endpoint.channel_id;
+    remove_sync_channel(deps.storage, channel_id)?;
+
+    Ok(IbcBasicResponse::new()
+        .add_attribute("action", "ibc_channel_close")
+        .add_attribute("channel_id", channel_id))
+}
+
+/// Failures are returned to the canonical registry in the acknowledgement
+/// rather than failing the transaction, so receive must not write to state
+/// before it fails.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_packet_receive(
+    deps: DepsMut,
+    env: Env,
+    msg: IbcPacketReceiveMsg,
+) -> Result<IbcReceiveResponse, ContractError> {
+    let response = IbcReceiveResponse::new().add_attribute("action", "ibc_packet_receive");
+    match receive(deps, &env, &msg.packet) {
+        Ok((attributes, event)) => Ok(response
+            .set_ack(to_json_binary(&RegistryAck::Result(Binary::default()))?)
+            .add_attribute("success", "true")
+            .add_attributes(attributes)
+            .add_event(event)),
+        Err(error) => Ok(response
+            .set_ack(to_json_binary(&RegistryAck::Error(error.to_string()))?)
+            .add_attribute("success", "false")
+            .add_attribute("error", error.to_string())),
+    }
+}
+
+/// Updates are applied in sequence, so an update that arrives before the
+/// one preceding it is rejected and resent by the canonical registry.
+fn receive(
+    mut deps: DepsMut,
+    env: &Env,
+    packet: &IbcPacket,
+) -> Result<(Vec<Attribute>, Event), ContractError> {
+    assert_writable(deps.as_ref(), env)?;
+
+    let channel_id = &packet.dest.channel_id;
+    if TRUSTED_CHANNEL.may_load(deps.storage)?.as_ref() != Some(channel_id) {
+        return Err(ContractError::UntrustedChannel(channel_id.clone()));
+    }
+
+    let SyncPacket { sequence, update } = from_json(&packet.data)?;
+    let expected = RECEIVED_SEQUENCES
+        .may_load(deps.storage, channel_id)?
+        .unwrap_or_default()
+        + 1;
+    if sequence != expected {
+        return Err(ContractError::UnexpectedSequence { expected, sequence });
+    }
+
+    let applied = apply_update(deps.branch(), update)?;
+    RECEIVED_SEQUENCES.save(deps.storage, channel_id, &sequence)?;
+    Ok(applied)
+}
+
+/// Stops pushing updates to channel_id and drops those awaiting resending.
+pub fn remove_sync_channel(storage: &mut dyn Storage, channel_id: &str) -> StdResult<()> {
+    SYNC_CHANNELS.remove(storage, channel_id);
+    let sequences = UNDELIVERED_UPDATES
+        .prefix(channel_id)
+        .keys(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<u64>>>()?;
+    for sequence in sequences {
+        UNDELIVERED_UPDATES.remove(storage, (channel_id, sequence));
+    }
+    Ok(())
+}
+
+/// Keeps an update the follower did not apply so it may be resent.
+fn save_undelivered(deps: DepsMut, packet: &IbcPacket) -> Result<(), ContractError> {
+    let channel_id = &packet.src.channel_id;
+    if SYNC_CHANNELS.has(deps.storage, channel_id) {
+        let SyncPacket { sequence, update } = from_json(&packet.data)?;
+        UNDELIVERED_UPDATES.save(deps.storage, (channel_id, sequence), &update)?;
+    }
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_packet_ack(
+    deps: DepsMut,
+    _env: Env,
+    msg: IbcPacketAckMsg,
+) -> Result<IbcBasicResponse, ContractError> {
+    let response = IbcBasicResponse::new()
+        .add_attribute("action", "ibc_packet_ack")
+        .add_attribute("channel_id", &msg.original_packet.src.channel_id);
+    match from_json(&msg.acknowledgement.data)? {
+        RegistryAck::Result(_) => Ok(response.add_attribute("success", "true")),
+        RegistryAck::Error(error) => {
+            save_undelivered(deps, &msg.original_packet)?;
+            Ok(response
+                .add_attribute("success", "false")
+                .add_attribute("error", error))
+        }
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_packet_timeout(
+    deps: DepsMut,
+    _env: Env,
+    msg: IbcPacketTimeoutMsg,
+) -> Result<IbcBasicResponse, ContractError> {
+    save_undelivered(deps, &msg.packet)?;
+    Ok(IbcBasicResponse::new()
+        .add_attribute("action", "ibc_packet_timeout")
+        .add_attribute("channel_id", msg.packet.src.channel_id))
+}
+
+/// Message sending packet over channel_id.
+pub fn sync_msg(env: &Env, channel_id: String, packet: &SyncPacket) -> StdResult<IbcMsg> {
+    Ok(IbcMsg::SendPacket {
+        channel_id,
+        data: to_json_binary(packet)?,
+        timeout: IbcTimeout::with_timestamp(env.block.time.plus_seconds(PACKET_LIFETIME)),
+    })
+}
+
+/// Messages pushing a registration update to every connected registry, each
+/// with the next sequence of its channel.
+pub fn sync_msgs(deps: DepsMut, env: &Env, update: &RegistryPacket) -> StdResult<Vec<IbcMsg>> {
+    let channels = SYNC_CHANNELS
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<(String, u64)>>>()?;
+    channels
+        .into_iter()
+        .map(|(channel_id, sequence)| {
+            let sequence = sequence + 1;
+            SYNC_CHANNELS.save(deps.storage, &channel_id, &sequence)?;
+            sync_msg(
+                env,
+                channel_id,
+                &SyncPacket {
+                    sequence,
+                    update: update.clone(),
+                },
+            )
+        })
+        .collect()
+}
@@ -1,9 +1,20 @@
 pub mod contract;
 mod error;
+pub mod events;
+#[cfg(feature = "library")]
+pub mod helpers;
+pub mod ibc;
+#[cfg(feature = "manifest")]
+pub mod manifest;
 pub mod msg;
 pub mod state;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+mod validation;
 
 #[cfg(test)]
 mod tests;
 
 pub use crate::error::ContractError;
+#[cfg(feature = "library")]
+pub use crate::helpers::CodeIdRegistryContract;
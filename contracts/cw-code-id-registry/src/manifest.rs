@@ -0,0 +1,177 @@
+use std::collections::BTreeMap;
+
+use thiserror::Error;
+
+use crate::error::ContractError;
+use crate::msg::{ExecuteMsg, RegisterMsg};
+use crate::validation::{validate_contract_name, validate_version};
+
+/// Suffix cosmwasm/workspace-optimizer gives artifacts built on ARM.
+const ARM_SUFFIX: &str = "-aarch64";
+
+#[derive(Error, Debug)]
+#[cfg_attr(test, derive(PartialEq))]
+pub enum ManifestError {
+    #[error("{0}")]
+    Contract(#[from] ContractError),
+
+    #[error("Line {0} is not of the form `<checksum> <file>`")]
+    InvalidChecksumLine(usize),
+
+    #[error("Line {0} is not of the form `<file> <code_id>`")]
+    InvalidLogLine(usize),
+
+    #[error("No code ID was stored for {0}")]
+    MissingCodeId(String),
+}
+
+/// An artifact listed in the checksums.txt produced by
+/// cosmwasm/workspace-optimizer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Artifact {
+    pub file: String,
+    /// Hex encoded SHA-256 hash of the artifact.
+    pub checksum: String,
+}
+
+/// The file name of a path, so artifacts may be referred to as either
+/// `artifacts/name.wasm` or `name.wasm`.
+fn file_name(path: &str) -> &str {
+    path.rsplit('/').next().unwrap_or(path)
+}
+
+/// Parses lines of the form `<checksum> <file>`.
+pub fn parse_checksums(checksums: &str) -> Result<Vec<Artifact>, ManifestError> {
+    checksums
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(
+            |(index, line)| match line.split_whitespace().collect::<Vec<_>>()[..] {
+                [checksum, file]
+                    if checksum.len() == 64 && checksum.chars().all(|c| c.is_ascii_hexdigit()) =>
+                {
+                    Ok(Artifact {
+                        file: file_name(file).to_string(),
+                        checksum: checksum.to_lowercase(),
+                    })
+                }
+                _ => Err(ManifestError::InvalidChecksumLine(index + 1)),
+            },
+        )
+        .collect()
+}
+
+/// Parses a store-code log of lines of the form `<file> <code_id>`,
+/// mapping each file to the code ID its upload was stored as. Empty lines
+/// and lines starting with `#` are skipped.
+pub fn parse_store_code_log(log: &str) -> Result<BTreeMap<String, u64>, ManifestError> {
+    log.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty() && !line.trim_start().starts_with('#'))
+        .map(
+            |(index, line)| match line.split_whitespace().collect::<Vec<_>>()[..] {
+                [file, code_id] => code_id
+                    .parse()
+                    .map(|code_id| (file_name(file).to_string(), code_id))
+                    .map_err(|_| ManifestError::InvalidLogLine(index + 1)),
+                _ => Err(ManifestError::InvalidLogLine(index + 1)),
+            },
+        )
+        .collect()
+}
+
+/// The contract name of an artifact. The optimizer names artifacts after
+/// the crate with hyphens replaced by underscores, so `cw_core.wasm` and
+/// `cw_core-aarch64.wasm` are both `cw-core`.
+pub fn contract_name(file: &str) -> String {
+    let name = file.strip_suffix(".wasm").unwrap_or(file);
+    let name = name.strip_suffix(ARM_SUFFIX).unwrap_or(name);
+    name.replace('_', "-")
+}
+
+/// Builds a RegisterBatch registering every artifact in checksums at
+/// version on chain_id, under the code ID the store-code log gives it.
+pub fn register_batch(
+    checksums: &str,
+    store_code_log: &str,
+    chain_id: &str,
+    version: &str,
+) -> Result<ExecuteMsg, ManifestError> {
+    validate_version(version)?;
+    let code_ids = parse_store_code_log(store_code_log)?;
+
+    let registrations = parse_checksums(checksums)?
+        .into_iter()
+        .map(|Artifact { file, checksum }| {
+            let code_id = *code_ids
+                .get(&file)
+                .ok_or_else(|| ManifestError::MissingCodeId(file.clone()))?;
+            let contract_name = contract_name(&file);
+            validate_contract_name(&contract_name)?;
+            Ok(RegisterMsg {
+                contract_name,
+                version: version.to_string(),
+                chain_id: chain_id.to_string(),
+                code_id,
+                checksum,
+            })
+        })
+        .collect::<Result<Vec<_>, ManifestError>>()?;
+
+    Ok(ExecuteMsg::RegisterBatch { registrations })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_register_batch() {
+        let checksums = format!(
+            "{}  cw_core.wasm\n{}  cw_proposal_single-aarch64.wasm\n",
+            "AB".repeat(32),
+            "cd".repeat(32)
+        );
+        let log = "# file code_id\nartifacts/cw_core.wasm 12\ncw_proposal_single-aarch64.wasm 13\n";
+
+        assert_eq!(
+            register_batch(&checksums, log, "juno-1", "2.0.0").unwrap(),
+            ExecuteMsg::RegisterBatch {
+                registrations: vec![
+                    RegisterMsg {
+                        contract_name: "cw-core".to_string(),
+                        version: "2.0.0".to_string(),
+                        chain_id: "juno-1".to_string(),
+                        code_id: 12,
+                        checksum: "ab".repeat(32),
+                    },
+                    RegisterMsg {
+                        contract_name: "cw-proposal-single".to_string(),
+                        version: "2.0.0".to_string(),
+                        chain_id: "juno-1".to_string(),
+                        code_id: 13,
+                        checksum: "cd".repeat(32),
+                    },
+                ]
+            }
+        );
+
+        assert_eq!(
+            register_batch(&checksums, "cw_core.wasm 12", "juno-1", "2.0.0").unwrap_err(),
+            ManifestError::MissingCodeId("cw_proposal_single-aarch64.wasm".to_string())
+        );
+        assert_eq!(
+            register_batch(&checksums, "cw_core.wasm twelve", "juno-1", "2.0.0").unwrap_err(),
+            ManifestError::InvalidLogLine(1)
+        );
+        assert_eq!(
+            register_batch("abc  cw_core.wasm", log, "juno-1", "2.0.0").unwrap_err(),
+            ManifestError::InvalidChecksumLine(1)
+        );
+        assert!(matches!(
+            register_batch(&checksums, log, "juno-1", "2.0").unwrap_err(),
+            ManifestError::Contract(ContractError::InvalidVersion(..))
+        ));
+    }
+}
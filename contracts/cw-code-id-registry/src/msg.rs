@@ -1,4 +1,10 @@
-use crate::state::Registration;
+use crate::state::{
+    ChainInfo, Delegation, Dependency, InstanceInfo, Interface, MessageSchemas, MigrationEdge,
+    NamespaceInfo, QueuedAction, RateLimit, Registration, RegistrationMode, RegistrationProposal,
+    Signer, SignerSet, SyncPeer, ValidationRules,
+};
+use cosmwasm_std::{Addr, Binary, Coin};
+use cw_utils::Expiration;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -11,7 +17,11 @@ pub struct InstantiateMsg {
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case", deny_unknown_fields)]
 pub enum ExecuteMsg {
-    /// Register code ID. May only be called by contract admin.
+    /// Register code ID. May only be called by contract admin, or for
+    /// names of the form `namespace/name` by the namespace owner and its
+    /// delegates. Depending on the registration mode, others may register
+    /// new names or propose registrations. Anyone but the admin must pay
+    /// the registration fee.
     Register {
         contract_name: String,
         version: String,
@@ -19,15 +29,240 @@ pub enum ExecuteMsg {
         code_id: u64,
         checksum: String,
     },
-    /// Allow admin to unregister code IDs.
+    /// Allow admin to unregister code IDs, or the namespace owner and its
+    /// delegates for namespaced names.
     Unregister {
         contract_name: String,
         chain_id: String,
         code_id: u64,
         version: String,
     },
+    /// Register many code IDs at once. Either all registrations succeed or
+    /// none do.
+    RegisterBatch { registrations: Vec<RegisterMsg> },
+    /// Unregister many code IDs at once. Either all unregistrations succeed
+    /// or none do.
+    UnregisterBatch { registrations: Vec<UnregisterMsg> },
+    /// Instantiate the latest version of a contract registered on the
+    /// current chain. If version_req provided, instantiates the latest
+    /// version matching it. funds must equal the funds sent. Responds with
+    /// InstantiateByNameResponse data.
+    InstantiateByName {
+        name: String,
+        version_req: Option<String>,
+        msg: Binary,
+        label: String,
+        admin: Option<String>,
+        funds: Vec<Coin>,
+    },
+    /// Instantiate a version of a contract registered on the current chain
+    /// with instantiate2, so that its address may be predicted with
+    /// QueryMsg::PredictAddress using this contract as the creator. funds
+    /// must equal the funds sent. Responds with InstantiateByNameResponse
+    /// data.
+    Instantiate2ByName {
+        name: String,
+        version: String,
+        msg: Binary,
+        label: String,
+        admin: Option<String>,
+        funds: Vec<Coin>,
+        salt: Binary,
+    },
+    /// Record a contract running registered code on the current chain as an
+    /// instance of its registration. May be called by anyone. Reporting an
+    /// instance again updates its code ID, e.g. after a migration.
+    ReportInstance { address: String },
+    /// Declare that contract_name may be migrated from from_version to
+    /// to_version. May be called by those who may register contract_name.
+    AddMigrationEdge {
+        contract_name: String,
+        from_version: String,
+        to_version: String,
+        migrate_msg: Option<String>,
+        breaking: bool,
+    },
+    /// May be called by those who may register contract_name.
+    RemoveMigrationEdge {
+        contract_name: String,
+        from_version: String,
+        to_version: String,
+    },
+    /// Migrate a contract the registry is the admin of to a newer version of
+    /// the same contract registered on the current chain. May only be called
+    /// by contract admin or whoever had the registry instantiate the contract.
+    MigrateToVersion {
+        contract_addr: String,
+        name: String,
+        version: String,
+        msg: Binary,
+    },
+    /// Replace the dependencies of a contract version. May be called by
+    /// those who may register contract_name.
+    SetDependencies {
+        contract_name: String,
+        version: String,
+        dependencies: Vec<Dependency>,
+    },
+    /// Replace the interfaces a contract version implements. May be called
+    /// by those who may register contract_name.
+    SetInterfaces {
+        contract_name: String,
+        version: String,
+        interfaces: Vec<Interface>,
+    },
+    /// Set the hashes of a contract version's message schemas. May be
+    /// called by those who may register contract_name.
+    SetSchemas {
+        contract_name: String,
+        version: String,
+        schemas: MessageSchemas,
+    },
+    /// Only accept registration updates from the canonical registry on the
+    /// other end of channel_id, or accept local updates again if None. May
+    /// only be called by the admin.
+    SetTrustedChannel { channel_id: Option<String> },
+    /// Allow the registry at port_id on the other end of connection_id to
+    /// open sync channels. May only be called by the admin.
+    AddSyncPeer {
+        connection_id: String,
+        port_id: String,
+    },
+    /// Stop a peer from opening new sync channels. Its open channels are
+    /// closed with CloseSyncChannel. May only be called by the admin.
+    RemoveSyncPeer {
+        connection_id: String,
+        port_id: String,
+    },
+    /// Stop pushing updates to channel_id and close it. May only be called
+    /// by the admin.
+    CloseSyncChannel { channel_id: String },
+    /// Resend, in order, the updates to channel_id that its follower
+    /// rejected or that timed out. May only be called by the admin.
+    Resync { channel_id: String },
+    /// Apply the registry updates in payload, a JSON encoded SignedPayload,
+    /// if it is signed by enough registry signers. Secp256k1 signers sign
+    /// the SHA-256 hash of payload and ed25519 signers sign payload itself.
+    /// Registrations are validated as if registered by the admin, and
+    /// unregistrations are queued behind the timelock delay if there is
+    /// one. May be called by anyone, but not on follower registries.
+    ImportSigned {
+        payload: Binary,
+        signatures: Vec<PayloadSignature>,
+    },
+    /// Replace the registry signers, once the timelock delay has passed if
+    /// there is one. May only be called by the admin.
+    SetSigners {
+        signers: Vec<Signer>,
+        threshold: u32,
+    },
+    /// Reject writes until `until`, or until unpaused if None. May be called
+    /// by the admin or guardian, though the guardian may only extend an
+    /// active pause.
+    Pause { until: Option<Expiration> },
+    /// Accept writes again. May only be called by the admin.
+    Unpause {},
+    /// Make the registry read-only for good, rejecting open registration
+    /// proposals and refunding their fees. May only be called by the admin.
+    Freeze {},
+    /// Queue Unregister, UnregisterBatch, unregistrations imported by
+    /// ImportSigned, UpdateAdmin, SetTimelockDelay, SetGuardian and
+    /// SetSigners for this many blocks before they may be executed. Zero
+    /// disables the timelock. May only be called by the admin, and is
+    /// itself timelocked.
+    SetTimelockDelay { blocks: u64 },
+    /// Execute a queued action whose delay has passed. May be called by
+    /// anyone.
+    ExecuteQueued { id: u64 },
+    /// Cancel a queued action. May be called by the admin or guardian,
+    /// though only the admin may cancel guardian changes.
+    CancelQueued { id: u64 },
+    /// Set or remove the guardian. May only be called by the admin, and is
+    /// timelocked.
+    SetGuardian { guardian: Option<UncheckedGuardian> },
+    /// Let delegate register up to max_registrations of names every period
+    /// blocks until expiration, replacing any existing delegation to it. May
+    /// only be called by the admin.
+    Delegate {
+        delegate: String,
+        names: Vec<String>,
+        max_registrations: u64,
+        period: u64,
+        expiration: Option<Expiration>,
+    },
+    /// May only be called by the admin.
+    RevokeDelegation { delegate: String },
+    /// Update the fields of the config that are set. May only be called by
+    /// the admin.
+    UpdateConfig {
+        registration_mode: Option<RegistrationMode>,
+        validation: Option<ValidationRules>,
+        registration_fee: Option<Vec<Coin>>,
+        rate_limit: Option<RateLimitUpdate>,
+    },
+    /// Register a proposed registration. May only be called by the admin.
+    ApproveRegistration { id: u64 },
+    /// Refund a proposed registration's fee and drop it. Allowed while the
+    /// registry is paused. May only be called by the admin.
+    RejectRegistration { id: u64 },
     /// Update admin.
     UpdateAdmin { admin: String },
+    /// Add a chain to the catalog of chains code IDs may be registered
+    /// on. May only be called by contract admin.
+    AddChain {
+        chain_id: String,
+        bech32_prefix: String,
+        pretty_name: String,
+        is_testnet: bool,
+    },
+    /// Register a namespace owned by owner. Namespaces may only be
+    /// registered once. May only be called by contract admin.
+    RegisterNamespace { namespace: String, owner: String },
+    /// Transfer ownership of a namespace. May only be called by the
+    /// namespace owner.
+    UpdateNamespaceOwner { namespace: String, owner: String },
+    /// Allow delegate to register and unregister names in a namespace. May
+    /// only be called by the namespace owner.
+    AddNamespaceDelegate { namespace: String, delegate: String },
+    /// May only be called by the namespace owner.
+    RemoveNamespaceDelegate { namespace: String, delegate: String },
+}
+
+/// A single entry of ExecuteMsg::RegisterBatch.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct RegisterMsg {
+    pub contract_name: String,
+    pub version: String,
+    pub chain_id: String,
+    pub code_id: u64,
+    pub checksum: String,
+}
+
+/// A single entry of ExecuteMsg::UnregisterBatch.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct UnregisterMsg {
+    pub contract_name: String,
+    pub chain_id: String,
+    pub code_id: u64,
+    pub version: String,
+}
+
+/// A guardian whose address has not been validated.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub enum UncheckedGuardian {
+    Address(String),
+    Cw4Group(String),
+}
+
+/// Sets or removes the registry's rate limit.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub enum RateLimitUpdate {
+    Set(RateLimit),
+    Remove {},
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
@@ -45,10 +280,111 @@ pub enum QueryMsg {
         chain_id: String,
         code_id: u64,
     },
+    /// Looks up many registrations at once. Results are in the same order
+    /// as requests, with None for requests that were not found.
+    GetRegistrations {
+        requests: Vec<RegistrationRequest>,
+    },
+    /// Looks up many code IDs on a chain at once. Results are in the same
+    /// order as code_ids, with None for code IDs that were not found.
+    GetCodeIdInfos {
+        chain_id: String,
+        code_ids: Vec<u64>,
+    },
     ListRegistrations {
         name: String,
         chain_id: String,
     },
+    /// Computes the address of a contract instantiated with instantiate2 by
+    /// creator on the current chain from the registered checksum.
+    PredictAddress {
+        name: String,
+        version: String,
+        creator: String,
+        salt: Binary,
+    },
+    /// Lists recorded instances of a code ID.
+    ListInstances {
+        chain_id: String,
+        code_id: u64,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Looks up the registration of the code a contract on the current chain
+    /// is running.
+    GetInstanceRegistration {
+        address: String,
+    },
+    /// Walks migration edges from the version a code ID is registered as to
+    /// the newest version reachable on the chain. Returns the shortest path
+    /// of migrations to it, which is empty if there is nothing newer.
+    UpgradePath {
+        chain_id: String,
+        code_id: u64,
+    },
+    GetDependencies {
+        name: String,
+        version: String,
+    },
+    /// Picks a version registered on the chain for every contract in the
+    /// dependency tree of name at version, such that every dependency's
+    /// version requirement is met. Prefers newer versions.
+    ResolveDependencies {
+        name: String,
+        version: String,
+        chain_id: String,
+    },
+    GetInterfaces {
+        name: String,
+        version: String,
+    },
+    /// Lists registrations on a chain that implement interface, ordered by
+    /// name and version. start_after is the (name, version) of the last
+    /// registration returned.
+    ListByInterface {
+        interface: String,
+        chain_id: String,
+        start_after: Option<(String, String)>,
+        limit: Option<u32>,
+    },
+    GetSchemas {
+        name: String,
+        version: String,
+    },
+    SyncChannels {},
+    /// Updates to channel_id awaiting ExecuteMsg::Resync.
+    ListUndeliveredUpdates {
+        channel_id: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    Signers {},
+    PauseInfo {},
+    Config {},
+    ListRegistrationProposals {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Lists delegations which have not expired.
+    ListDelegations {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    ListQueuedActions {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    GetChain {
+        chain_id: String,
+    },
+    /// If is_testnet provided, only returns chains that are (or are not)
+    /// testnets. Otherwise returns all chains.
+    ListChains {
+        is_testnet: Option<bool>,
+    },
+    GetNamespace {
+        namespace: String,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
@@ -57,8 +393,212 @@ pub struct GetRegistrationResponse {
     pub registration: Registration,
 }
 
+/// A single entry of QueryMsg::GetRegistrations. If version provided,
+/// tries to find given version. Otherwise finds the latest version
+/// registered.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct RegistrationRequest {
+    pub name: String,
+    pub chain_id: String,
+    pub version: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct GetRegistrationsResponse {
+    pub registrations: Vec<Option<Registration>>,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
 #[serde(rename_all = "snake_case", deny_unknown_fields)]
 pub struct ListRegistrationsResponse {
     pub registrations: Vec<Registration>,
 }
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct GetChainResponse {
+    pub chain: ChainInfo,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct ListChainsResponse {
+    pub chains: Vec<ChainInfo>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct GetNamespaceResponse {
+    pub namespace: NamespaceInfo,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct ListInstancesResponse {
+    pub instances: Vec<InstanceInfo>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct UpgradeStep {
+    pub edge: MigrationEdge,
+    /// The registration to migrate to.
+    pub registration: Registration,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct UpgradePathResponse {
+    pub current: Registration,
+    pub path: Vec<UpgradeStep>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct GetDependenciesResponse {
+    pub dependencies: Vec<Dependency>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct ResolveDependenciesResponse {
+    /// One registration per contract in the dependency tree, ordered by
+    /// contract name.
+    pub registrations: Vec<Registration>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct GetInterfacesResponse {
+    pub interfaces: Vec<Interface>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct InterfaceImplementation {
+    pub interface: Interface,
+    pub registration: Registration,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct ListByInterfaceResponse {
+    pub implementations: Vec<InterfaceImplementation>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct GetSchemasResponse {
+    pub schemas: MessageSchemas,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct SyncChannelsResponse {
+    pub trusted_channel: Option<String>,
+    pub channels: Vec<String>,
+    pub peers: Vec<SyncPeer>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct ListUndeliveredUpdatesResponse {
+    pub updates: Vec<SyncPacket>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct SignersResponse {
+    pub signer_set: Option<SignerSet>,
+    pub next_nonce: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct PauseInfoResponse {
+    /// None if the registry is not paused.
+    pub paused_until: Option<Expiration>,
+    pub frozen: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct ListRegistrationProposalsResponse {
+    pub proposals: Vec<RegistrationProposal>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct ListDelegationsResponse {
+    pub delegations: Vec<Delegation>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct ListQueuedActionsResponse {
+    pub actions: Vec<QueuedAction>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct PredictAddressResponse {
+    pub address: Addr,
+    pub registration: Registration,
+}
+
+/// Data set on the response to ExecuteMsg::InstantiateByName and
+/// ExecuteMsg::Instantiate2ByName.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct InstantiateByNameResponse {
+    pub contract_address: Addr,
+    pub registration: Registration,
+}
+
+/// Registration updates pushed from the canonical registry to followers.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub enum RegistryPacket {
+    Register(RegisterMsg),
+    Unregister(UnregisterMsg),
+}
+
+/// A registration update pushed over a sync channel. Sequences start at 1
+/// and increase by one per update sent over the channel, so followers can
+/// apply updates in the order they were made and detect gaps.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct SyncPacket {
+    pub sequence: u64,
+    pub update: RegistryPacket,
+}
+
+/// Acknowledgement of a SyncPacket.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub enum RegistryAck {
+    Result(Binary),
+    Error(String),
+}
+
+/// Registry updates signed by the registry signers, so that anyone may relay
+/// them to registries on chains without IBC connectivity.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct SignedPayload {
+    /// The chain and address of the registry the payload is for, so it
+    /// can't be replayed on other registries with the same signers.
+    pub chain_id: String,
+    pub registry: String,
+    pub nonce: u64,
+    pub updates: Vec<RegistryPacket>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct PayloadSignature {
+    pub public_key: Binary,
+    pub signature: Binary,
+}
@@ -1,8 +1,11 @@
-use cosmwasm_std::Addr;
+use cosmwasm_std::{Addr, Binary, Coin, Empty};
 use cw_storage_plus::{Item, Map};
+use cw_utils::Expiration;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use crate::msg::{RegisterMsg, RegistryPacket, UnregisterMsg};
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
 #[serde(rename_all = "snake_case", deny_unknown_fields)]
 pub struct Registration {
@@ -12,11 +15,341 @@ pub struct Registration {
     pub checksum: String,
 }
 
-/// The admin has sole permissions to register code IDs.
-pub const ADMIN: Item<Addr> = Item::new("admin");
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct ChainInfo {
+    pub chain_id: String,
+    pub bech32_prefix: String,
+    pub pretty_name: String,
+    pub is_testnet: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct NamespaceInfo {
+    pub namespace: String,
+    pub owner: Addr,
+    pub delegates: Vec<Addr>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct InstanceInfo {
+    pub address: Addr,
+    pub chain_id: String,
+    pub code_id: u64,
+    /// Set if the registry instantiated the contract, to who asked it to.
+    pub instantiated_by: Option<Addr>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct PendingInstantiation {
+    pub registration: Registration,
+    pub sender: Addr,
+}
+
+/// Declares that a contract may be migrated from one version to another.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct MigrationEdge {
+    pub contract_name: String,
+    pub from_version: String,
+    pub to_version: String,
+    /// A template or JSON schema of the migrate message to send.
+    pub migrate_msg: Option<String>,
+    /// Whether the migration needs attention beyond sending the message,
+    /// e.g. because it changes the contract's interface.
+    pub breaking: bool,
+}
+
+/// Declares that a contract version only works with versions of another
+/// contract matching version_req.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct Dependency {
+    pub contract_name: String,
+    pub version_req: String,
+}
+
+/// A standard interface a contract implements, e.g. `cw20` or
+/// `dao-voting-module`, at a schema version.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct Interface {
+    pub name: String,
+    pub version: String,
+}
+
+/// Hex encoded SHA-256 hashes of a contract version's JSON schemas, so
+/// clients can check they hold the right schema for its code.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct MessageSchemas {
+    pub instantiate_msg: Option<String>,
+    pub execute_msg: Option<String>,
+    pub query_msg: Option<String>,
+    pub migrate_msg: Option<String>,
+    /// Where the schemas may be downloaded from, e.g. an IPFS URI.
+    pub uri: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub enum KeyType {
+    Secp256k1,
+    Ed25519,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct Signer {
+    pub key_type: KeyType,
+    pub public_key: Binary,
+}
+
+/// Registry signers, threshold of whom must sign a payload for it to be
+/// imported.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct SignerSet {
+    pub signers: Vec<Signer>,
+    pub threshold: u32,
+}
+
+/// Destructive actions which wait out the timelock delay before they may be
+/// executed.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub enum TimelockedAction {
+    Unregister(UnregisterMsg),
+    UpdateAdmin {
+        admin: Addr,
+    },
+    SetTimelockDelay {
+        blocks: u64,
+    },
+    SetGuardian {
+        guardian: Option<Guardian>,
+    },
+    SetSigners {
+        signers: Vec<Signer>,
+        threshold: u32,
+    },
+}
+
+/// The guardian may pause the registry and veto queued actions, but may not
+/// publish.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub enum Guardian {
+    Address(Addr),
+    /// Any member of the cw4 group with a weight of at least one.
+    Cw4Group(Addr),
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct QueuedAction {
+    pub id: u64,
+    pub action: TimelockedAction,
+    pub proposer: Addr,
+    /// Anyone may execute the action from this height on.
+    pub executable_at: u64,
+}
+
+/// Release rights the admin has delegated, e.g. from a DAO to a sub-DAO or
+/// release bot.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct Delegation {
+    pub delegate: Addr,
+    /// The names the delegate may register.
+    pub names: Vec<String>,
+    /// How many registrations the delegate may make per period.
+    pub max_registrations: u64,
+    /// Length of a period in blocks.
+    pub period: u64,
+    pub expiration: Expiration,
+    /// Height the current period started at.
+    pub period_start: u64,
+    /// Registrations made in the current period.
+    pub used: u64,
+}
+
+/// Limits on how much publishers other than the admin may register per
+/// period.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct RateLimit {
+    /// Length of a period in blocks.
+    pub period: u64,
+    pub max_registrations: Option<u64>,
+    /// Maximum number of names not yet registered on any chain.
+    pub max_new_names: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct PublisherUsage {
+    /// Height the current period started at.
+    pub period_start: u64,
+    pub registrations: u64,
+    pub new_names: u64,
+}
+
+/// Who may register names outside of a namespace.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RegistrationMode {
+    /// Only the admin and its delegates may register.
+    AdminOnly,
+    /// Anyone may register a name nobody has registered yet, and only they
+    /// may register it from then on.
+    Open,
+    /// Registrations by anyone other than the admin and its delegates are
+    /// proposed for the admin to approve or reject.
+    Proposal,
+}
+
+/// Rules registrations must follow on top of the contract name and semver
+/// grammar.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct ValidationRules {
+    /// Maximum length of the namespace and of the name in a contract name,
+    /// at most 64.
+    pub max_name_length: u32,
+    /// Whether versions like `1.0.0-beta.1` may be registered.
+    pub allow_prerelease: bool,
+    /// Whether checksums must be hex encoded SHA-256 hashes.
+    pub require_checksum: bool,
+}
+
+impl Default for ValidationRules {
+    fn default() -> Self {
+        Self {
+            max_name_length: 64,
+            allow_prerelease: true,
+            require_checksum: false,
+        }
+    }
+}
+
+/// Every policy knob of the registry.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct Config {
+    /// The admin has sole permissions to register code IDs outside of a
+    /// namespace, unless the registration mode says otherwise.
+    pub admin: Addr,
+    pub registration_mode: RegistrationMode,
+    pub validation: ValidationRules,
+    /// Paid to the admin for each registration by anyone but the admin.
+    pub registration_fee: Vec<Coin>,
+    /// Writes are rejected until this expires.
+    pub paused_until: Option<Expiration>,
+    /// Frozen registries are read-only for good.
+    pub frozen: bool,
+    /// Number of blocks timelocked actions are queued for. Actions are not
+    /// queued if zero.
+    pub timelock_delay: u64,
+    pub guardian: Option<Guardian>,
+    pub rate_limit: Option<RateLimit>,
+}
+
+/// A registration awaiting the admin's approval.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct RegistrationProposal {
+    pub id: u64,
+    pub proposer: Addr,
+    pub registration: RegisterMsg,
+    /// Fee paid by the proposer, held until the proposal is approved or
+    /// rejected.
+    pub fee: Vec<Coin>,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
 /// Map (name, chain_id, version) to a code_id.
 pub const NAME_CHAIN_ID_VERSION_TO_REGISTRATION: Map<(&str, &str, &str), Registration> =
     Map::new("name_chain_id_version_to_code_id");
 /// Map (name, chain_id, code_id) to the registration.
 pub const CHAIN_ID_CODE_ID_TO_REGISTRATION: Map<(&str, u64), Registration> =
     Map::new("chain_id_code_id_to_registration");
+/// Map chain_id to the chain's info. Code IDs may only be registered on
+/// chains in this catalog.
+pub const CHAINS: Map<&str, ChainInfo> = Map::new("chains");
+/// Map namespace to its info. Only the namespace owner and its delegates
+/// may register contract names of the form `namespace/name`.
+pub const NAMESPACES: Map<&str, NamespaceInfo> = Map::new("namespaces");
+/// Map reply ID to the registration being instantiated by
+/// ExecuteMsg::InstantiateByName or ExecuteMsg::Instantiate2ByName, loaded
+/// when the instantiation replies.
+/// Contracts instantiated by name may themselves instantiate by name, so
+/// each instantiation gets its own reply ID.
+pub const PENDING_INSTANTIATIONS: Map<u64, PendingInstantiation> =
+    Map::new("pending_instantiations");
+pub const NEXT_REPLY_ID: Item<u64> = Item::new("next_reply_id");
+/// Map contract address to the instance the registry has recorded for it.
+pub const INSTANCES: Map<&Addr, InstanceInfo> = Map::new("instances");
+/// Map (chain_id, code_id, address) to nothing, indexing INSTANCES by
+/// code ID.
+pub const CHAIN_ID_CODE_ID_INSTANCES: Map<(&str, u64, &Addr), Empty> =
+    Map::new("chain_id_code_id_instances");
+/// Map (name, from_version, to_version) to the migration edge between
+/// them.
+pub const MIGRATION_EDGES: Map<(&str, &str, &str), MigrationEdge> = Map::new("migration_edges");
+/// Map (name, version) to the contracts that version depends on.
+pub const DEPENDENCIES: Map<(&str, &str), Vec<Dependency>> = Map::new("dependencies");
+/// Map (name, version) to the interfaces that version implements.
+pub const INTERFACES: Map<(&str, &str), Vec<Interface>> = Map::new("interfaces");
+/// Map (interface, name, version) to the interface version, indexing
+/// INTERFACES by interface.
+pub const INTERFACE_IMPLEMENTATIONS: Map<(&str, &str, &str), String> =
+    Map::new("interface_implementations");
+/// Map (name, version) to the hashes of that version's message schemas.
+pub const SCHEMAS: Map<(&str, &str), MessageSchemas> = Map::new("schemas");
+
+/// A registry allowed to open sync channels, identified by its port on the
+/// other end of an IBC connection.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct SyncPeer {
+    pub connection_id: String,
+    pub port_id: String,
+}
+
+/// Map (connection, counterparty port) of approved sync peers.
+pub const SYNC_PEERS: Map<(&str, &str), Empty> = Map::new("sync_peers");
+/// Map IBC channels registration updates are pushed to to the sequence of
+/// the last update sent over them.
+pub const SYNC_CHANNELS: Map<&str, u64> = Map::new("sync_channels");
+/// Map (channel, sequence) to updates sent over channel that were rejected
+/// or timed out, so they may be resent with ExecuteMsg::Resync.
+pub const UNDELIVERED_UPDATES: Map<(&str, u64), RegistryPacket> = Map::new("undelivered_updates");
+/// Map channels to the sequence of the last update applied from them.
+pub const RECEIVED_SEQUENCES: Map<&str, u64> = Map::new("received_sequences");
+/// If set, this registry follows the canonical registry on the other end of
+/// this channel and only accepts registration updates from it.
+pub const TRUSTED_CHANNEL: Item<String> = Item::new("trusted_channel");
+
+pub const SIGNER_SET: Item<SignerSet> = Item::new("signer_set");
+/// The nonce the next signed payload must carry, so payloads are imported
+/// in order and only once.
+pub const IMPORT_NONCE: Item<u64> = Item::new("import_nonce");
+
+pub const QUEUED_ACTIONS: Map<u64, QueuedAction> = Map::new("queued_actions");
+pub const NEXT_ACTION_ID: Item<u64> = Item::new("next_action_id");
+
+pub const DELEGATIONS: Map<&Addr, Delegation> = Map::new("delegations");
+
+/// Map publisher to its registrations in the current rate limit period.
+pub const PUBLISHER_USAGE: Map<&Addr, PublisherUsage> = Map::new("publisher_usage");
+
+/// Map flat name to whoever first registered it in open registration mode.
+pub const NAME_PUBLISHERS: Map<&str, Addr> = Map::new("name_publishers");
+
+pub const REGISTRATION_PROPOSALS: Map<u64, RegistrationProposal> =
+    Map::new("registration_proposals");
+pub const NEXT_PROPOSAL_ID: Item<u64> = Item::new("next_proposal_id");
@@ -0,0 +1,161 @@
+use anyhow::Result as AnyResult;
+use cosmwasm_std::{Addr, Empty, StdError, StdResult};
+use cw_multi_test::{App, Contract, ContractWrapper, Executor};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::msg::{
+    ExecuteMsg, GetRegistrationsResponse, InstantiateMsg, QueryMsg, RegisterMsg,
+    RegistrationRequest,
+};
+use crate::state::ChainInfo;
+
+/// Bech32 prefix the app's own chain is added to the catalog with.
+pub const MOCK_BECH32_PREFIX: &str = "cosmos";
+
+pub fn registry_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        crate::contract::execute,
+        crate::contract::instantiate,
+        crate::contract::query,
+    )
+    .with_reply(crate::contract::reply);
+    Box::new(contract)
+}
+
+/// State to pre-populate a mock registry with, e.g. parsed from a JSON file.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct Fixture {
+    /// Chains to add to the catalog. The app's own chain is always added.
+    #[serde(default)]
+    pub chains: Vec<ChainInfo>,
+    /// Registered by the admin in order after the chains are added.
+    #[serde(default)]
+    pub registrations: Vec<RegisterMsg>,
+}
+
+/// A registry running in a cw-multi-test App.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MockRegistry {
+    pub addr: Addr,
+    pub admin: Addr,
+    /// The app's chain ID, which code stored with store_and_register is
+    /// registered on.
+    pub chain_id: String,
+}
+
+impl MockRegistry {
+    /// Instantiates a registry administered by admin, adds the app's chain
+    /// to its catalog and applies fixture.
+    pub fn new(app: &mut App, admin: &Addr, fixture: Fixture) -> AnyResult<Self> {
+        let code_id = app.store_code(registry_contract());
+        let addr = app.instantiate_contract(
+            code_id,
+            admin.clone(),
+            &InstantiateMsg {
+                admin: admin.to_string(),
+            },
+            &[],
+            "Code ID Registry",
+            None,
+        )?;
+        let registry = MockRegistry {
+            addr,
+            admin: admin.clone(),
+            chain_id: app.block_info().chain_id,
+        };
+
+        let mut chains = fixture.chains;
+        if !chains
+            .iter()
+            .any(|chain| chain.chain_id == registry.chain_id)
+        {
+            chains.insert(
+                0,
+                ChainInfo {
+                    chain_id: registry.chain_id.clone(),
+                    bech32_prefix: MOCK_BECH32_PREFIX.to_string(),
+                    pretty_name: registry.chain_id.clone(),
+                    is_testnet: true,
+                },
+            );
+        }
+        for chain in chains {
+            registry.execute(
+                app,
+                ExecuteMsg::AddChain {
+                    chain_id: chain.chain_id,
+                    bech32_prefix: chain.bech32_prefix,
+                    pretty_name: chain.pretty_name,
+                    is_testnet: chain.is_testnet,
+                },
+            )?;
+        }
+        if !fixture.registrations.is_empty() {
+            registry.execute(
+                app,
+                ExecuteMsg::RegisterBatch {
+                    registrations: fixture.registrations,
+                },
+            )?;
+        }
+
+        Ok(registry)
+    }
+
+    /// Executes msg as the admin.
+    pub fn execute(&self, app: &mut App, msg: ExecuteMsg) -> AnyResult<()> {
+        app.execute_contract(self.admin.clone(), self.addr.clone(), &msg, &[])?;
+        Ok(())
+    }
+
+    /// Stores code in the app and registers it as name at version on the
+    /// app's chain, so it may be resolved and instantiated by name. As the
+    /// app does not compute checksums, the checksum registered is the hex
+    /// encoded SHA-256 hash of `name@version`.
+    pub fn store_and_register(
+        &self,
+        app: &mut App,
+        name: &str,
+        version: &str,
+        code: Box<dyn Contract<Empty>>,
+    ) -> AnyResult<u64> {
+        let code_id = app.store_code(code);
+        let checksum = hex::encode(Sha256::digest(format!("{}@{}", name, version).as_bytes()));
+        self.execute(
+            app,
+            ExecuteMsg::Register {
+                contract_name: name.to_string(),
+                version: version.to_string(),
+                chain_id: self.chain_id.clone(),
+                code_id,
+                checksum,
+            },
+        )?;
+        Ok(code_id)
+    }
+
+    /// The code ID name is registered with on the app's chain. If version
+    /// is None, the latest version registered.
+    pub fn code_id(&self, app: &App, name: &str, version: Option<&str>) -> StdResult<u64> {
+        let response: GetRegistrationsResponse = app.wrap().query_wasm_smart(
+            self.addr.clone(),
+            &QueryMsg::GetRegistrations {
+                requests: vec![RegistrationRequest {
+                    name: name.to_string(),
+                    chain_id: self.chain_id.clone(),
+                    version: version.map(String::from),
+                }],
+            },
+        )?;
+        response
+            .registrations
+            .into_iter()
+            .flatten()
+            .next()
+            .map(|registration| registration.code_id)
+            .ok_or_else(|| StdError::not_found(format!("{} {:?}", name, version)))
+    }
+}
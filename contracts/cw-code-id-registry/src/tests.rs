@@ -1,34 +1,96 @@
+use crate::events::{EVENT_VERSION, REGISTER_EVENT, UNREGISTER_EVENT, UPDATE_ADMIN_EVENT};
+use crate::ibc::{
+    ibc_channel_connect, ibc_channel_open, ibc_packet_ack, ibc_packet_receive, ibc_packet_timeout,
+    IBC_VERSION,
+};
 use crate::msg::{
-    ExecuteMsg, GetCodeIdInfoResponse, GetRegistrationResponse, InstantiateMsg,
-    ListRegistrationsResponse, QueryMsg,
+    ExecuteMsg, GetChainResponse, GetDependenciesResponse, GetInterfacesResponse,
+    GetNamespaceResponse, GetRegistrationResponse, GetRegistrationsResponse, GetSchemasResponse,
+    InstantiateByNameResponse, InstantiateMsg, ListByInterfaceResponse, ListChainsResponse,
+    ListDelegationsResponse, ListInstancesResponse, ListQueuedActionsResponse,
+    ListRegistrationProposalsResponse, ListRegistrationsResponse, ListUndeliveredUpdatesResponse,
+    PauseInfoResponse, PayloadSignature, PredictAddressResponse, QueryMsg, RateLimitUpdate,
+    RegisterMsg, RegistrationRequest, RegistryAck, RegistryPacket, ResolveDependenciesResponse,
+    SignedPayload, SignersResponse, SyncChannelsResponse, SyncPacket, UncheckedGuardian,
+    UnregisterMsg, UpgradePathResponse,
+};
+use crate::state::{
+    ChainInfo, Config, Delegation, Dependency, Guardian, InstanceInfo, Interface, KeyType,
+    MessageSchemas, NamespaceInfo, QueuedAction, RateLimit, Registration, RegistrationMode,
+    RegistrationProposal, Signer, SignerSet, SyncPeer, TimelockedAction, ValidationRules,
 };
-use crate::state::Registration;
+use crate::testing::{registry_contract, Fixture, MockRegistry};
 use crate::ContractError;
 use anyhow::Result as AnyResult;
-use cosmwasm_std::{coin, coins, to_binary, Addr, Coin, Empty, StdResult, Uint128};
-use cw20::{BalanceResponse, Cw20Coin};
+use bech32::{FromBase32, ToBase32, Variant};
+use cosmwasm_std::testing::{
+    mock_dependencies, mock_env, mock_ibc_channel_connect_ack, mock_ibc_channel_connect_confirm,
+    mock_ibc_channel_open_try, mock_ibc_packet_ack, mock_ibc_packet_recv, mock_ibc_packet_timeout,
+    mock_info, MockApi, MockQuerier, MockStorage,
+};
+use cosmwasm_std::{
+    coins, from_json, to_json_binary, Addr, Api, Binary, CanonicalAddr, Coin, CosmosMsg, Deps,
+    DepsMut, Empty, Env, Event, IbcAcknowledgement, IbcMsg, IbcOrder, MessageInfo, OverflowError,
+    OverflowOperation, OwnedDeps, RecoverPubkeyError, Reply, Response, StdError, StdResult, SubMsg,
+    SubMsgResponse, SubMsgResult, Uint128, VerificationError, WasmMsg,
+};
 use cw_multi_test::{App, AppResponse, Contract, ContractWrapper, Executor};
-use cw_utils::PaymentError;
+use cw_utils::{Expiration, PaymentError};
+use std::marker::PhantomData;
 
 const USER_ADDR: &str = "user";
 const OTHER_USER_ADDR: &str = "other_user";
 const ADMIN_ADDR: &str = "admin";
 const CHAIN_ID: &str = "chain-id";
 
-fn cw20_contract() -> Box<dyn Contract<Empty>> {
-    let contract = ContractWrapper::new(
-        cw20_base::contract::execute,
-        cw20_base::contract::instantiate,
-        cw20_base::contract::query,
-    );
-    Box::new(contract)
+/// A contract that does nothing but may be migrated.
+fn migratable_contract() -> Box<dyn Contract<Empty>> {
+    fn instantiate(_: DepsMut, _: Env, _: MessageInfo, _: Empty) -> StdResult<Response> {
+        Ok(Response::new())
+    }
+    fn execute(_: DepsMut, _: Env, _: MessageInfo, _: Empty) -> StdResult<Response> {
+        Ok(Response::new())
+    }
+    fn query(_: Deps, _: Env, _: Empty) -> StdResult<Binary> {
+        to_json_binary(&Empty {})
+    }
+    fn migrate(_: DepsMut, _: Env, _: Empty) -> StdResult<Response> {
+        Ok(Response::new())
+    }
+    Box::new(ContractWrapper::new(execute, instantiate, query).with_migrate(migrate))
+}
+
+/// A contract that instantiates cw-migratable by name from the registry it
+/// is instantiated with.
+fn factory_contract() -> Box<dyn Contract<Empty>> {
+    fn instantiate(_: DepsMut, _: Env, _: MessageInfo, registry: String) -> StdResult<Response> {
+        Ok(Response::new().add_message(WasmMsg::Execute {
+            contract_addr: registry,
+            msg: to_json_binary(&ExecuteMsg::InstantiateByName {
+                name: "cw-migratable".to_string(),
+                version_req: None,
+                msg: to_json_binary(&Empty {})?,
+                label: "child".to_string(),
+                admin: None,
+                funds: vec![],
+            })?,
+            funds: vec![],
+        }))
+    }
+    fn execute(_: DepsMut, _: Env, _: MessageInfo, _: Empty) -> StdResult<Response> {
+        Ok(Response::new())
+    }
+    fn query(_: Deps, _: Env, _: Empty) -> StdResult<Binary> {
+        to_json_binary(&Empty {})
+    }
+    Box::new(ContractWrapper::new(execute, instantiate, query))
 }
 
-fn registry_contract() -> Box<dyn Contract<Empty>> {
+fn cw4_group_contract() -> Box<dyn Contract<Empty>> {
     let contract = ContractWrapper::new(
-        crate::contract::execute,
-        crate::contract::instantiate,
-        crate::contract::query,
+        cw4_group::contract::execute,
+        cw4_group::contract::instantiate,
+        cw4_group::contract::query,
     );
     Box::new(contract)
 }
@@ -36,128 +98,93 @@ fn registry_contract() -> Box<dyn Contract<Empty>> {
 fn setup_app() -> App {
     let amount = Uint128::new(10000);
     App::new(|r, _a, s| {
-        r.bank
-            .init_balance(
-                s,
-                &Addr::unchecked(USER_ADDR),
-                vec![
-                    Coin {
-                        denom: "ujuno".to_string(),
-                        amount,
-                    },
-                    Coin {
-                        denom: "uatom".to_string(),
-                        amount,
-                    },
-                ],
-            )
-            .unwrap();
-        r.bank
-            .init_balance(
-                s,
-                &Addr::unchecked(OTHER_USER_ADDR),
-                vec![
-                    Coin {
-                        denom: "ujuno".to_string(),
-                        amount,
-                    },
-                    Coin {
-                        denom: "uatom".to_string(),
-                        amount,
-                    },
-                ],
-            )
-            .unwrap();
-        r.bank
-            .init_balance(
-                s,
-                &Addr::unchecked(ADMIN_ADDR),
-                vec![
-                    Coin {
-                        denom: "ujuno".to_string(),
-                        amount,
-                    },
-                    Coin {
-                        denom: "uatom".to_string(),
-                        amount,
-                    },
-                ],
-            )
-            .unwrap();
+        for addr in [USER_ADDR, OTHER_USER_ADDR, ADMIN_ADDR] {
+            r.bank
+                .init_balance(
+                    s,
+                    &Addr::unchecked(addr),
+                    vec![
+                        Coin {
+                            denom: "ujuno".to_string(),
+                            amount,
+                        },
+                        Coin {
+                            denom: "uatom".to_string(),
+                            amount,
+                        },
+                    ],
+                )
+                .unwrap();
+        }
     })
 }
 
-fn create_token(app: &mut App) -> Addr {
-    let cw20_id = app.store_code(cw20_contract());
-    app.instantiate_contract(
-        cw20_id,
-        Addr::unchecked(ADMIN_ADDR),
-        &cw20_base::msg::InstantiateMsg {
-            name: "Name Registry Token".to_string(),
-            symbol: "NAME".to_string(),
-            decimals: 6,
-            initial_balances: vec![
-                Cw20Coin {
-                    address: USER_ADDR.to_string(),
-                    amount: Uint128::new(1000),
-                },
-                Cw20Coin {
-                    address: ADMIN_ADDR.to_string(),
-                    amount: Uint128::new(1000),
-                },
-                Cw20Coin {
-                    address: OTHER_USER_ADDR.to_string(),
-                    amount: Uint128::new(1000),
-                },
-            ],
-            mint: None,
-            marketing: None,
-        },
-        &[],
-        "some token",
-        None,
-    )
-    .unwrap()
-}
-
 fn setup_test_case(app: &mut App) -> Addr {
     let code_id = app.store_code(registry_contract());
-    app.instantiate_contract(
-        code_id,
+    let contract = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked(ADMIN_ADDR),
+            &InstantiateMsg {
+                admin: ADMIN_ADDR.to_string(),
+            },
+            &[],
+            "Code ID Registry",
+            None,
+        )
+        .unwrap();
+
+    add_chain(
+        app,
+        contract.clone(),
+        CHAIN_ID.to_string(),
+        false,
         Addr::unchecked(ADMIN_ADDR),
-        &InstantiateMsg {
-            admin: ADMIN_ADDR.to_string(),
-        },
-        &[],
-        "Code ID Registry",
-        None,
     )
-    .unwrap()
+    .unwrap();
+
+    contract
 }
 
-#[test]
-fn test_instantiate() {
-    let mut app = setup_app();
-    let token_addr = create_token(&mut app);
-    let code_id = app.store_code(registry_contract());
+fn add_chain(
+    app: &mut App,
+    contract_addr: Addr,
+    chain_id: String,
+    is_testnet: bool,
+    sender: Addr,
+) -> AnyResult<AppResponse> {
+    let msg = ExecuteMsg::AddChain {
+        chain_id: chain_id.clone(),
+        bech32_prefix: "juno".to_string(),
+        pretty_name: chain_id,
+        is_testnet,
+    };
+    app.execute_contract(sender, contract_addr, &msg, &[])
+}
 
-    app.instantiate_contract(
+fn register(
+    app: &mut App,
+    contract_addr: Addr,
+    name: String,
+    version: String,
+    code_id: u64,
+    sender: Addr,
+) -> AnyResult<AppResponse> {
+    let msg = ExecuteMsg::Register {
+        contract_name: name,
+        checksum: version.clone(),
+        version,
+        chain_id: CHAIN_ID.to_string(),
         code_id,
-        Addr::unchecked(ADMIN_ADDR),
-        &InstantiateMsg {
-            admin: ADMIN_ADDR.to_string(),
-        },
-        &[],
-        "Code ID Registry",
-        None,
-    )
-    .unwrap();
+    };
+    app.execute_contract(sender, contract_addr, &msg, &[])
 }
 
 fn unregister(
     app: &mut App,
     contract_addr: Addr,
     name: String,
+    version: String,
     code_id: u64,
     sender: Addr,
 ) -> AnyResult<AppResponse> {
@@ -165,21 +192,29 @@ fn unregister(
         contract_name: name,
         chain_id: CHAIN_ID.to_string(),
         code_id,
+        version,
     };
     app.execute_contract(sender, contract_addr, &msg, &[])
 }
 
-fn update_config(
+fn update_admin(
     app: &mut App,
     contract_addr: Addr,
-    admin: Option<String>,
-    payment_info: Option<PaymentInfo>,
+    admin: String,
     sender: Addr,
 ) -> AnyResult<AppResponse> {
-    let msg = ExecuteMsg::UpdateAdmin {
-        admin,
-        payment_info,
-    };
+    let msg = ExecuteMsg::UpdateAdmin { admin };
+    app.execute_contract(sender, contract_addr, &msg, &[])
+}
+
+fn register_namespace(
+    app: &mut App,
+    contract_addr: Addr,
+    namespace: String,
+    owner: String,
+    sender: Addr,
+) -> AnyResult<AppResponse> {
+    let msg = ExecuteMsg::RegisterNamespace { namespace, owner };
     app.execute_contract(sender, contract_addr, &msg, &[])
 }
 
@@ -201,7 +236,7 @@ fn query_info_for_code_id(
     app: &mut App,
     contract_addr: Addr,
     code_id: u64,
-) -> StdResult<GetCodeIdInfoResponse> {
+) -> StdResult<GetRegistrationResponse> {
     let msg = QueryMsg::GetCodeIdInfo {
         chain_id: CHAIN_ID.to_string(),
         code_id,
@@ -221,20 +256,61 @@ fn query_list_registrations(
     app.wrap().query_wasm_smart(contract_addr, &msg)
 }
 
-fn query_config(app: &mut App, contract_addr: Addr) -> Config {
+fn query_admin(app: &mut App, contract_addr: Addr) -> Addr {
     let msg = QueryMsg::Admin {};
     app.wrap().query_wasm_smart(contract_addr, &msg).unwrap()
 }
 
+fn query_list_chains(
+    app: &mut App,
+    contract_addr: Addr,
+    is_testnet: Option<bool>,
+) -> Vec<ChainInfo> {
+    let msg = QueryMsg::ListChains { is_testnet };
+    let response: ListChainsResponse = app.wrap().query_wasm_smart(contract_addr, &msg).unwrap();
+    response.chains
+}
+
+#[test]
+fn test_instantiate() {
+    let mut app = setup_app();
+    let contract = setup_test_case(&mut app);
+
+    assert_eq!(query_admin(&mut app, contract), Addr::unchecked(ADMIN_ADDR));
+}
+
 #[test]
 fn test_register() {
     let mut app = setup_app();
     let contract = setup_test_case(&mut app);
-    let other_token = create_token(&mut app); // To be used when sending wrong token
-    let name: &str = "Name";
+    let name: &str = "name";
     let version: &str = "0.0.1";
     let code_id: u64 = 1;
 
+    // Only admin may register.
+    let err: ContractError = register(
+        &mut app,
+        contract.clone(),
+        name.to_string(),
+        version.to_string(),
+        code_id,
+        Addr::unchecked(USER_ADDR),
+    )
+    .unwrap_err()
+    .downcast()
+    .unwrap();
+    assert_eq!(err, ContractError::UnauthorizedRegistration {});
+
+    register(
+        &mut app,
+        contract.clone(),
+        name.to_string(),
+        version.to_string(),
+        code_id,
+        Addr::unchecked(ADMIN_ADDR),
+    )
+    .unwrap();
+
     // Check registration with and without version.
     let resp_without_version =
         query_get_registration(&mut app, contract.clone(), name.to_string(), None).unwrap();
@@ -248,7 +324,7 @@ fn test_register() {
     assert_eq!(
         resp_without_version.registration,
         Registration {
-            registered_by: Addr::unchecked(USER_ADDR),
+            contract_name: name.to_string(),
             version: version.to_string(),
             code_id,
             checksum: version.to_string(),
@@ -258,7 +334,25 @@ fn test_register() {
         resp_without_version.registration,
         resp_with_version.registration,
     );
+    assert_eq!(
+        query_info_for_code_id(&mut app, contract.clone(), code_id)
+            .unwrap()
+            .registration,
+        resp_with_version.registration,
+    );
 
+    // Should fail with Code ID already registered.
+    let err: ContractError = register(
+        &mut app,
+        contract,
+        name.to_string(),
+        "0.0.2".to_string(),
+        code_id,
+        Addr::unchecked(ADMIN_ADDR),
+    )
+    .unwrap_err()
+    .downcast()
+    .unwrap();
     assert_eq!(
         err,
         ContractError::CodeIDAlreadyRegistered(code_id, CHAIN_ID.to_string())
@@ -266,448 +360,4016 @@ fn test_register() {
 }
 
 #[test]
-// fn test_immutability() {
-//     let mut app = setup_app();
-//     let pay_denom = "ujuno";
-//     let contract = setup_test_case(&mut app);
-//     let name: &str = "Name";
-//     let version: &str = "0.0.1";
-//     let code_id: u64 = 1;
-//     // Check registration with and without version.
-//     let resp_without_version =
-//         query_get_registration(&mut app, contract.clone(), name.to_string(), None).unwrap();
-//     let resp_with_version = query_get_registration(
-//         &mut app,
-//         contract.clone(),
-//         name.to_string(),
-//         Some(version.to_string()),
-//     )
-//     .unwrap();
-//     assert_eq!(
-//         resp_without_version.registration,
-//         Registration {
-//             registered_by: Addr::unchecked(USER_ADDR),
-//             version: version.to_string(),
-//             code_id,
-//             checksum: version.to_string(),
-//         }
-//     );
-//     assert_eq!(
-//         resp_without_version.registration,
-//         resp_with_version.registration,
-//     );
-// }
+fn test_unregister() {
+    let mut app = setup_app();
+    let contract = setup_test_case(&mut app);
+    let name: &str = "contract";
+
+    let registrations: Vec<Registration> = (1..=3)
+        .map(|code_id| Registration {
+            contract_name: name.to_string(),
+            version: format!("0.0.{}", code_id),
+            code_id,
+            checksum: format!("0.0.{}", code_id),
+        })
+        .collect();
+    for registration in registrations.iter() {
+        register(
+            &mut app,
+            contract.clone(),
+            name.to_string(),
+            registration.version.clone(),
+            registration.code_id,
+            Addr::unchecked(ADMIN_ADDR),
+        )
+        .unwrap();
+    }
+    let (reg1, reg2, reg3) = (
+        registrations[0].clone(),
+        registrations[1].clone(),
+        registrations[2].clone(),
+    );
+
+    // Get all registrations and verify all exist.
+    let response = query_list_registrations(&mut app, contract.clone(), name.to_string()).unwrap();
+    assert_eq!(response.registrations, registrations);
+
+    // Get latest and ensure it is 3.
+    let latest_registration =
+        query_get_registration(&mut app, contract.clone(), name.to_string(), None)
+            .unwrap()
+            .registration;
+    assert_eq!(latest_registration, reg3);
+
+    // Attempt unregister 3 by user but fail because not admin.
+    let err: ContractError = unregister(
+        &mut app,
+        contract.clone(),
+        name.to_string(),
+        reg3.version.clone(),
+        reg3.code_id,
+        Addr::unchecked(USER_ADDR),
+    )
+    .unwrap_err()
+    .downcast()
+    .unwrap();
+    assert_eq!(err, ContractError::UnauthorizedRegistration {});
+
+    // Unregister 3.
+    unregister(
+        &mut app,
+        contract.clone(),
+        name.to_string(),
+        reg3.version.clone(),
+        reg3.code_id,
+        Addr::unchecked(ADMIN_ADDR),
+    )
+    .unwrap();
+
+    // Attempt to get info for 3 and expect not found.
+    let err = query_info_for_code_id(&mut app, contract.clone(), reg3.code_id).unwrap_err();
+    assert!(err
+        .to_string()
+        .contains(&ContractError::NotFound {}.to_string()));
+    // Attempt to get registration for 3 and expect not found.
+    let err = query_get_registration(
+        &mut app,
+        contract.clone(),
+        name.to_string(),
+        Some(reg3.version.clone()),
+    )
+    .unwrap_err();
+    assert!(err
+        .to_string()
+        .contains(&ContractError::NotFound {}.to_string()));
+
+    // Get latest and ensure it is 2.
+    let latest_registration =
+        query_get_registration(&mut app, contract.clone(), name.to_string(), None)
+            .unwrap()
+            .registration;
+    assert_eq!(latest_registration, reg2);
+
+    // Get all registrations and verify only 1 and 2 exist.
+    let response = query_list_registrations(&mut app, contract.clone(), name.to_string()).unwrap();
+    assert_eq!(response.registrations, vec![reg1.clone(), reg2.clone()]);
+
+    // Unregister 1 and 2.
+    for registration in [reg1, reg2] {
+        unregister(
+            &mut app,
+            contract.clone(),
+            name.to_string(),
+            registration.version,
+            registration.code_id,
+            Addr::unchecked(ADMIN_ADDR),
+        )
+        .unwrap();
+    }
+
+    // Expect not found when attempting to get latest.
+    let err =
+        query_get_registration(&mut app, contract.clone(), name.to_string(), None).unwrap_err();
+    assert!(err
+        .to_string()
+        .contains(&ContractError::NotFound {}.to_string()));
+
+    // Get all registrations and expect empty list found.
+    let response = query_list_registrations(&mut app, contract, name.to_string()).unwrap();
+    assert_eq!(response.registrations.len(), 0);
+}
+
+#[test]
+fn test_update_admin() {
+    let mut app = setup_app();
+    let contract = setup_test_case(&mut app);
+
+    // Update admin as non admin fails.
+    let err: ContractError = update_admin(
+        &mut app,
+        contract.clone(),
+        OTHER_USER_ADDR.to_string(),
+        Addr::unchecked(OTHER_USER_ADDR),
+    )
+    .unwrap_err()
+    .downcast()
+    .unwrap();
+    assert_eq!(err, ContractError::UnauthorizedUpdateAdmin {});
+    assert_eq!(
+        query_admin(&mut app, contract.clone()),
+        Addr::unchecked(ADMIN_ADDR)
+    );
+
+    // Update admin as admin.
+    update_admin(
+        &mut app,
+        contract.clone(),
+        OTHER_USER_ADDR.to_string(),
+        Addr::unchecked(ADMIN_ADDR),
+    )
+    .unwrap();
+    assert_eq!(
+        query_admin(&mut app, contract.clone()),
+        Addr::unchecked(OTHER_USER_ADDR)
+    );
+
+    // Old admin may no longer register.
+    let err: ContractError = register(
+        &mut app,
+        contract,
+        "name".to_string(),
+        "0.0.1".to_string(),
+        1,
+        Addr::unchecked(ADMIN_ADDR),
+    )
+    .unwrap_err()
+    .downcast()
+    .unwrap();
+    assert_eq!(err, ContractError::UnauthorizedRegistration {});
+}
+
+#[test]
+fn test_chains() {
+    let mut app = setup_app();
+    let contract = setup_test_case(&mut app);
+
+    // Only admin may add chains.
+    let err: ContractError = add_chain(
+        &mut app,
+        contract.clone(),
+        "uni-5".to_string(),
+        true,
+        Addr::unchecked(USER_ADDR),
+    )
+    .unwrap_err()
+    .downcast()
+    .unwrap();
+    assert_eq!(err, ContractError::UnauthorizedAddChain {});
+
+    add_chain(
+        &mut app,
+        contract.clone(),
+        "uni-5".to_string(),
+        true,
+        Addr::unchecked(ADMIN_ADDR),
+    )
+    .unwrap();
+
+    // Chains may only be added once.
+    let err: ContractError = add_chain(
+        &mut app,
+        contract.clone(),
+        "uni-5".to_string(),
+        true,
+        Addr::unchecked(ADMIN_ADDR),
+    )
+    .unwrap_err()
+    .downcast()
+    .unwrap();
+    assert_eq!(err, ContractError::ChainAlreadyExists("uni-5".to_string()));
+
+    // Chain IDs with whitespace are rejected.
+    let err: ContractError = add_chain(
+        &mut app,
+        contract.clone(),
+        "Juno-1 ".to_string(),
+        false,
+        Addr::unchecked(ADMIN_ADDR),
+    )
+    .unwrap_err()
+    .downcast()
+    .unwrap();
+    assert_eq!(err, ContractError::InvalidChainId("Juno-1 ".to_string()));
+
+    let response: GetChainResponse = app
+        .wrap()
+        .query_wasm_smart(
+            contract.clone(),
+            &QueryMsg::GetChain {
+                chain_id: "uni-5".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        response.chain,
+        ChainInfo {
+            chain_id: "uni-5".to_string(),
+            bech32_prefix: "juno".to_string(),
+            pretty_name: "uni-5".to_string(),
+            is_testnet: true,
+        }
+    );
+
+    // Filter by mainnet and testnet.
+    let chains = query_list_chains(&mut app, contract.clone(), None);
+    assert_eq!(chains.len(), 2);
+    let chains = query_list_chains(&mut app, contract.clone(), Some(true));
+    assert_eq!(chains.len(), 1);
+    assert_eq!(chains[0].chain_id, "uni-5");
+    let chains = query_list_chains(&mut app, contract.clone(), Some(false));
+    assert_eq!(chains.len(), 1);
+    assert_eq!(chains[0].chain_id, CHAIN_ID);
+
+    // Registering on a chain not in the catalog fails.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(ADMIN_ADDR),
+            contract,
+            &ExecuteMsg::Register {
+                contract_name: "name".to_string(),
+                version: "0.0.1".to_string(),
+                chain_id: "juno-1".to_string(),
+                code_id: 1,
+                checksum: "checksum".to_string(),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::UnknownChain("juno-1".to_string()));
+}
+
 #[test]
-// fn test_unregister() {
-//     let mut app = setup_app();
-//     let version1: &str = "0.0.1";
-//     let code_id1: u64 = 1;
-//     let version2: &str = "0.0.2";
-//     let code_id2: u64 = 2;
-//     let version3: &str = "0.0.3";
-//     let code_id3: u64 = 3;
-//     let contract_name: String = "contract".to_string();
-
-//     let reg1 = Registration {
-//         version: version1.to_string(),
-//         code_id: code_id1,
-//         checksum: version1.to_string(),
-//         contract_name,
-//     };
-//     let reg2 = Registration {
-//         version: version2.to_string(),
-//         code_id: code_id2,
-//         checksum: version2.to_string(),
-//         contract_name,
-//     };
-//     let reg3 = Registration {
-//         version: version3.to_string(),
-//         code_id: code_id3,
-//         checksum: version3.to_string(),
-//         contract_name,
-//     };
-
-//     // Get all registrations and verify all exist.
-//     let registrations = query_list_registrations(&mut app, contract.clone(), name.to_string())
-//         .unwrap()
-//         .registrations;
-//     assert_eq!(
-//         registrations,
-//         vec![reg1.clone(), reg2.clone(), reg3.clone()]
-//     );
-
-//     // Get latest and ensure it is 3.
-//     let latest_registration =
-//         query_get_registration(&mut app, contract_name.clone(), name.to_string(), None)
-//             .unwrap()
-//             .registration;
-//     assert_eq!(latest_registration, reg3);
-
-//     // Attempt unregister 3 by user but fail because not admin.
-//     let err: ContractError = unregister(
-//         &mut app,
-//         contract.clone(),
-//         name.to_string(),
-//         code_id3,
-//         Addr::unchecked(USER_ADDR),
-//     )
-//     .unwrap_err()
-//     .downcast()
-//     .unwrap();
-//     assert_eq!(err, ContractError::UnauthorizedUpdateAdmin {});
-
-//     // Unregister 3
-//     unregister(
-//         &mut app,
-//         contract.clone(),
-//         name.to_string(),
-//         code_id3,
-//         Addr::unchecked(ADMIN_ADDR),
-//     )
-//     .unwrap();
-
-//     // Attempt to get info for 3 and expect not found.
-//     let err = query_info_for_code_id(&mut app, contract.clone(), code_id3).unwrap_err();
-//     assert!(err
-//         .to_string()
-//         .contains(&ContractError::NotFound {}.to_string()));
-//     // Attempt to get registration for 3 and expect not found.
-//     let err = query_get_registration(
-//         &mut app,
-//         contract.clone(),
-//         name.to_string(),
-//         Some(version3.to_string()),
-//     )
-//     .unwrap_err();
-//     assert!(err
-//         .to_string()
-//         .contains(&ContractError::NotFound {}.to_string()));
-
-//     // Get latest and ensure it is 2.
-//     let latest_registration =
-//         query_get_registration(&mut app, contract.clone(), name.to_string(), None)
-//             .unwrap()
-//             .registration;
-//     assert_eq!(latest_registration, reg2);
-
-//     // Get all registrations and verify only 1 and 2 exist.
-//     let registrations = query_list_registrations(&mut app, contract.clone(), name.to_string())
-//         .unwrap()
-//         .registrations;
-//     assert_eq!(registrations, vec![reg1, reg2]);
-
-//     // Unregister 1 and 2
-//     unregister(
-//         &mut app,
-//         contract.clone(),
-//         name.to_string(),
-//         code_id1,
-//         Addr::unchecked(ADMIN_ADDR),
-//     )
-//     .unwrap();
-//     unregister(
-//         &mut app,
-//         contract.clone(),
-//         name.to_string(),
-//         code_id2,
-//         Addr::unchecked(ADMIN_ADDR),
-//     )
-//     .unwrap();
-
-//     // Expect not found when attempting to get latest.
-//     let err =
-//         query_get_registration(&mut app, contract.clone(), name.to_string(), None).unwrap_err();
-//     assert!(err
-//         .to_string()
-//         .contains(&ContractError::NotFound {}.to_string()));
-
-//     // Get all registrations and expect empty list found.
-//     let response = query_list_registrations(&mut app, contract, name.to_string()).unwrap();
-//     assert_eq!(response.registrations.len(), 0);
-// }
+fn test_name_and_version_validation() {
+    let mut app = setup_app();
+    let contract = setup_test_case(&mut app);
+
+    let cases = [
+        ("", "0.1.0", ContractError::EmptyContractName {}),
+        (
+            "Name",
+            "0.1.0",
+            ContractError::InvalidContractNameChar("Name".to_string(), 'N'),
+        ),
+        (
+            "name\n",
+            "0.1.0",
+            ContractError::InvalidContractNameChar("name\n".to_string(), '\n'),
+        ),
+        (
+            "a/b/c",
+            "0.1.0",
+            ContractError::InvalidContractNamespace("a/b/c".to_string()),
+        ),
+        (
+            "/name",
+            "0.1.0",
+            ContractError::InvalidContractNamespace("/name".to_string()),
+        ),
+        (
+            &"a".repeat(65),
+            "0.1.0",
+            ContractError::ContractNameTooLong("a".repeat(65), 64),
+        ),
+    ];
+    for (name, version, expected) in cases {
+        let err: ContractError = register(
+            &mut app,
+            contract.clone(),
+            name.to_string(),
+            version.to_string(),
+            1,
+            Addr::unchecked(ADMIN_ADDR),
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+        assert_eq!(err, expected);
+    }
+
+    let err: ContractError = register(
+        &mut app,
+        contract.clone(),
+        "name".to_string(),
+        "v1".to_string(),
+        1,
+        Addr::unchecked(ADMIN_ADDR),
+    )
+    .unwrap_err()
+    .downcast()
+    .unwrap();
+    assert!(matches!(err, ContractError::InvalidVersion(version, _) if version == "v1"));
+
+    // Namespaced names with dashes and underscores are fine.
+    register_namespace(
+        &mut app,
+        contract.clone(),
+        "dao-dao".to_string(),
+        ADMIN_ADDR.to_string(),
+        Addr::unchecked(ADMIN_ADDR),
+    )
+    .unwrap();
+    register(
+        &mut app,
+        contract,
+        "dao-dao/cw_proposal-single".to_string(),
+        "1.0.0-beta.1".to_string(),
+        1,
+        Addr::unchecked(ADMIN_ADDR),
+    )
+    .unwrap();
+}
+
 #[test]
-// fn test_mutable_after_unregister() {
-//     let mut app = setup_app();
-//     let pay_denom = "ujuno";
-//     let contract = setup_test_case(
-//         &mut app,
-//         PaymentInfo::NativePayment {
-//             token_denom: pay_denom.to_string(),
-//             payment_amount: Uint128::new(50),
-//         },
-//     );
-//     let name: &str = "Name";
-//     let version: &str = "0.0.1";
-//     let code_id: u64 = 1;
-
-//     // Give user address ownership over name.
-//     set_owner(
-//         &mut app,
-//         contract.clone(),
-//         name.to_string(),
-//         Some(USER_ADDR.to_string()),
-//         Addr::unchecked(ADMIN_ADDR),
-//     )
-//     .unwrap();
-
-//     // Register.
-//     register_native(
-//         &mut app,
-//         contract.clone(),
-//         coins(50, pay_denom),
-//         name.to_string(),
-//         version.to_string(),
-//         code_id,
-//         Addr::unchecked(USER_ADDR),
-//     )
-//     .unwrap();
-
-//     // Check registration with and without version.
-//     let resp_without_version =
-//         query_get_registration(&mut app, contract.clone(), name.to_string(), None).unwrap();
-//     let resp_with_version = query_get_registration(
-//         &mut app,
-//         contract.clone(),
-//         name.to_string(),
-//         Some(version.to_string()),
-//     )
-//     .unwrap();
-//     assert_eq!(
-//         resp_without_version.registration,
-//         Registration {
-//             registered_by: Addr::unchecked(USER_ADDR),
-//             version: version.to_string(),
-//             code_id,
-//             checksum: version.to_string(),
-//         }
-//     );
-//     assert_eq!(
-//         resp_without_version.registration,
-//         resp_with_version.registration,
-//     );
-
-//     // Should fail with Code ID already registered.
-//     let err: ContractError = register_native(
-//         &mut app,
-//         contract.clone(),
-//         coins(50, pay_denom),
-//         name.to_string(),
-//         version.to_string(),
-//         code_id,
-//         Addr::unchecked(USER_ADDR),
-//     )
-//     .unwrap_err()
-//     .downcast()
-//     .unwrap();
-//     assert_eq!(
-//         err,
-//         ContractError::CodeIDAlreadyRegistered(code_id, CHAIN_ID.to_string())
-//     );
-
-//     // Should fail with version already registered.
-//     let err: ContractError = register_native(
-//         &mut app,
-//         contract.clone(),
-//         coins(50, pay_denom),
-//         name.to_string(),
-//         version.to_string(),
-//         code_id + 1,
-//         Addr::unchecked(USER_ADDR),
-//     )
-//     .unwrap_err()
-//     .downcast()
-//     .unwrap();
-//     assert_eq!(
-//         err,
-//         ContractError::VersionAlreadyRegistered(
-//             version.to_string(),
-//             name.to_string(),
-//             CHAIN_ID.to_string()
-//         )
-//     );
-
-//     // Unregister
-//     unregister(
-//         &mut app,
-//         contract.clone(),
-//         name.to_string(),
-//         code_id,
-//         Addr::unchecked(ADMIN_ADDR),
-//     )
-//     .unwrap();
-
-//     // Should NOT fail with Code ID already registered.
-//     register_native(
-//         &mut app,
-//         contract.clone(),
-//         coins(50, pay_denom),
-//         name.to_string(),
-//         version.to_string(),
-//         code_id,
-//         Addr::unchecked(USER_ADDR),
-//     )
-//     .unwrap();
-
-//     // Unregister
-//     unregister(
-//         &mut app,
-//         contract.clone(),
-//         name.to_string(),
-//         code_id,
-//         Addr::unchecked(ADMIN_ADDR),
-//     )
-//     .unwrap();
-
-//     // Should NOT fail with version already registered.
-//     register_native(
-//         &mut app,
-//         contract.clone(),
-//         coins(50, pay_denom),
-//         name.to_string(),
-//         version.to_string(),
-//         code_id + 1,
-//         Addr::unchecked(USER_ADDR),
-//     )
-//     .unwrap();
-
-//     // Get info for new code ID.
-//     let info = query_info_for_code_id(&mut app, contract, code_id + 1).unwrap();
-//     assert_eq!(
-//         info,
-//         GetCodeIdInfoResponse {
-//             registered_by: Addr::unchecked(USER_ADDR),
-//             name: name.to_string(),
-//             version: version.to_string(),
-//             checksum: version.to_string(),
-//         }
-//     );
-// }
+fn test_latest_is_semver_ordered() {
+    let mut app = setup_app();
+    let contract = setup_test_case(&mut app);
+    let name: &str = "name";
+
+    for (code_id, version) in [(1, "0.9.0"), (2, "0.10.0"), (3, "0.10.0-rc.1")] {
+        register(
+            &mut app,
+            contract.clone(),
+            name.to_string(),
+            version.to_string(),
+            code_id,
+            Addr::unchecked(ADMIN_ADDR),
+        )
+        .unwrap();
+    }
+
+    let latest_registration = query_get_registration(&mut app, contract, name.to_string(), None)
+        .unwrap()
+        .registration;
+    assert_eq!(latest_registration.version, "0.10.0");
+    assert_eq!(latest_registration.code_id, 2);
+}
 
 #[test]
-// fn test_update_config() {
-//     let mut app = setup_app();
-//     let token = create_token(&mut app);
-//     let names = setup_test_case(
-//         &mut app,
-//         PaymentInfo::Cw20Payment {
-//             token_address: token.to_string(),
-//             payment_amount: Uint128::new(50),
-//         },
-//     );
-//     let other_token = create_token(&mut app); // To be used when updating payment token
-
-//     let config = query_config(&mut app, names.clone());
-//     assert_eq!(
-//         config,
-//         Config {
-//             admin: Addr::unchecked(ADMIN_ADDR),
-//             payment_info: PaymentInfo::Cw20Payment {
-//                 token_address: token.to_string(),
-//                 payment_amount: Uint128::new(50)
-//             }
-//         }
-//     );
-
-//     // Update config as non admin fails
-//     let err: ContractError = update_config(
-//         &mut app,
-//         names.clone(),
-//         Some(other_token.to_string()),
-//         Some(PaymentInfo::NativePayment {
-//             token_denom: "ujuno".to_string(),
-//             payment_amount: Uint128::new(50),
-//         }),
-//         Addr::unchecked(OTHER_USER_ADDR),
-//     )
-//     .unwrap_err()
-//     .downcast()
-//     .unwrap();
-//     assert_eq!(err, ContractError::UnauthorizedUpdateAdmin {});
-
-//     // Ensure config stayed the same.
-//     let config = query_config(&mut app, names.clone());
-//     assert_eq!(
-//         config,
-//         Config {
-//             admin: Addr::unchecked(ADMIN_ADDR),
-//             payment_info: PaymentInfo::Cw20Payment {
-//                 token_address: token.to_string(),
-//                 payment_amount: Uint128::new(50)
-//             }
-//         }
-//     );
-
-//     // Update config as admin
-//     update_config(
-//         &mut app,
-//         names.clone(),
-//         Some(OTHER_USER_ADDR.to_string()),
-//         Some(PaymentInfo::NativePayment {
-//             token_denom: "ujuno".to_string(),
-//             payment_amount: Uint128::new(25),
-//         }),
-//         Addr::unchecked(ADMIN_ADDR),
-//     )
-//     .unwrap();
-
-//     let config = query_config(&mut app, names.clone());
-//     assert_eq!(
-//         config,
-//         Config {
-//             admin: Addr::unchecked(OTHER_USER_ADDR),
-//             payment_info: PaymentInfo::NativePayment {
-//                 token_denom: "ujuno".to_string(),
-//                 payment_amount: Uint128::new(25)
-//             }
-//         }
-//     );
-
-//     // Update one config value but not the others
-
-//     // Only admin
-//     update_config(
-//         &mut app,
-//         names.clone(),
-//         Some(ADMIN_ADDR.to_string()),
-//         None,
-//         Addr::unchecked(OTHER_USER_ADDR),
-//     )
-//     .unwrap();
-
-//     let config = query_config(&mut app, names.clone());
-//     assert_eq!(
-//         config,
-//         Config {
-//             admin: Addr::unchecked(ADMIN_ADDR), // Only this has changed
-//             payment_info: PaymentInfo::NativePayment {
-//                 token_denom: "ujuno".to_string(),
-//                 payment_amount: Uint128::new(25)
-//             }
-//         }
-//     );
-
-//     // Only payment info
-//     update_config(
-//         &mut app,
-//         names.clone(),
-//         None,
-//         Some(PaymentInfo::NativePayment {
-//             token_denom: "uatom".to_string(),
-//             payment_amount: Uint128::new(50),
-//         }),
-//         Addr::unchecked(ADMIN_ADDR),
-//     )
-//     .unwrap();
-
-//     let config = query_config(&mut app, names);
-//     assert_eq!(
-//         config,
-//         Config {
-//             admin: Addr::unchecked(ADMIN_ADDR),
-//             payment_info: PaymentInfo::NativePayment {
-//                 token_denom: "uatom".to_string(),
-//                 payment_amount: Uint128::new(50)
-//             }
-//         }
-//     );
-// }
+fn test_namespaces() {
+    let mut app = setup_app();
+    let contract = setup_test_case(&mut app);
+    let name: &str = "dao-dao/cw-core";
+
+    // Only admin may register namespaces.
+    let err: ContractError = register_namespace(
+        &mut app,
+        contract.clone(),
+        "dao-dao".to_string(),
+        USER_ADDR.to_string(),
+        Addr::unchecked(USER_ADDR),
+    )
+    .unwrap_err()
+    .downcast()
+    .unwrap();
+    assert_eq!(err, ContractError::UnauthorizedRegisterNamespace {});
+
+    // Can't register names in a namespace that doesn't exist.
+    let err: ContractError = register(
+        &mut app,
+        contract.clone(),
+        name.to_string(),
+        "0.1.0".to_string(),
+        1,
+        Addr::unchecked(ADMIN_ADDR),
+    )
+    .unwrap_err()
+    .downcast()
+    .unwrap();
+    assert_eq!(err, ContractError::UnknownNamespace("dao-dao".to_string()));
+
+    register_namespace(
+        &mut app,
+        contract.clone(),
+        "dao-dao".to_string(),
+        USER_ADDR.to_string(),
+        Addr::unchecked(ADMIN_ADDR),
+    )
+    .unwrap();
+
+    // Namespaces may only be registered once.
+    let err: ContractError = register_namespace(
+        &mut app,
+        contract.clone(),
+        "dao-dao".to_string(),
+        ADMIN_ADDR.to_string(),
+        Addr::unchecked(ADMIN_ADDR),
+    )
+    .unwrap_err()
+    .downcast()
+    .unwrap();
+    assert_eq!(
+        err,
+        ContractError::NamespaceAlreadyRegistered("dao-dao".to_string())
+    );
+
+    // Admin can't register inside someone else's namespace.
+    let err: ContractError = register(
+        &mut app,
+        contract.clone(),
+        name.to_string(),
+        "0.1.0".to_string(),
+        1,
+        Addr::unchecked(ADMIN_ADDR),
+    )
+    .unwrap_err()
+    .downcast()
+    .unwrap();
+    assert_eq!(err, ContractError::UnauthorizedRegistration {});
+
+    // Owner can, but may not register flat names.
+    register(
+        &mut app,
+        contract.clone(),
+        name.to_string(),
+        "0.1.0".to_string(),
+        1,
+        Addr::unchecked(USER_ADDR),
+    )
+    .unwrap();
+    let err: ContractError = register(
+        &mut app,
+        contract.clone(),
+        "cw-core".to_string(),
+        "0.1.0".to_string(),
+        2,
+        Addr::unchecked(USER_ADDR),
+    )
+    .unwrap_err()
+    .downcast()
+    .unwrap();
+    assert_eq!(err, ContractError::UnauthorizedRegistration {});
+
+    // Only owner may add delegates.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(OTHER_USER_ADDR),
+            contract.clone(),
+            &ExecuteMsg::AddNamespaceDelegate {
+                namespace: "dao-dao".to_string(),
+                delegate: OTHER_USER_ADDR.to_string(),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::UnauthorizedUpdateNamespace {});
+
+    app.execute_contract(
+        Addr::unchecked(USER_ADDR),
+        contract.clone(),
+        &ExecuteMsg::AddNamespaceDelegate {
+            namespace: "dao-dao".to_string(),
+            delegate: OTHER_USER_ADDR.to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Delegate may register and unregister.
+    register(
+        &mut app,
+        contract.clone(),
+        name.to_string(),
+        "0.2.0".to_string(),
+        2,
+        Addr::unchecked(OTHER_USER_ADDR),
+    )
+    .unwrap();
+    unregister(
+        &mut app,
+        contract.clone(),
+        name.to_string(),
+        "0.2.0".to_string(),
+        2,
+        Addr::unchecked(OTHER_USER_ADDR),
+    )
+    .unwrap();
+
+    let response: GetNamespaceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            contract.clone(),
+            &QueryMsg::GetNamespace {
+                namespace: "dao-dao".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        response.namespace,
+        NamespaceInfo {
+            namespace: "dao-dao".to_string(),
+            owner: Addr::unchecked(USER_ADDR),
+            delegates: vec![Addr::unchecked(OTHER_USER_ADDR)],
+        }
+    );
+
+    // Removed delegates lose access.
+    app.execute_contract(
+        Addr::unchecked(USER_ADDR),
+        contract.clone(),
+        &ExecuteMsg::RemoveNamespaceDelegate {
+            namespace: "dao-dao".to_string(),
+            delegate: OTHER_USER_ADDR.to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+    let err: ContractError = register(
+        &mut app,
+        contract.clone(),
+        name.to_string(),
+        "0.2.0".to_string(),
+        2,
+        Addr::unchecked(OTHER_USER_ADDR),
+    )
+    .unwrap_err()
+    .downcast()
+    .unwrap();
+    assert_eq!(err, ContractError::UnauthorizedRegistration {});
+
+    // Transfer ownership.
+    app.execute_contract(
+        Addr::unchecked(USER_ADDR),
+        contract.clone(),
+        &ExecuteMsg::UpdateNamespaceOwner {
+            namespace: "dao-dao".to_string(),
+            owner: OTHER_USER_ADDR.to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+    register(
+        &mut app,
+        contract,
+        name.to_string(),
+        "0.2.0".to_string(),
+        2,
+        Addr::unchecked(OTHER_USER_ADDR),
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_batch() {
+    let mut app = setup_app();
+    let contract = setup_test_case(&mut app);
+    let name: &str = "name";
+
+    let register_msg = |version: &str, code_id: u64| RegisterMsg {
+        contract_name: name.to_string(),
+        version: version.to_string(),
+        chain_id: CHAIN_ID.to_string(),
+        code_id,
+        checksum: version.to_string(),
+    };
+
+    // Second entry reuses a code ID, so nothing is registered.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(ADMIN_ADDR),
+            contract.clone(),
+            &ExecuteMsg::RegisterBatch {
+                registrations: vec![register_msg("0.1.0", 1), register_msg("0.2.0", 1)],
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::BatchEntry {
+            index: 1,
+            error: Box::new(ContractError::CodeIDAlreadyRegistered(
+                1,
+                CHAIN_ID.to_string()
+            )),
+        }
+    );
+    let response = query_list_registrations(&mut app, contract.clone(), name.to_string()).unwrap();
+    assert!(response.registrations.is_empty());
+
+    let response = app
+        .execute_contract(
+            Addr::unchecked(ADMIN_ADDR),
+            contract.clone(),
+            &ExecuteMsg::RegisterBatch {
+                registrations: vec![
+                    register_msg("0.1.0", 1),
+                    register_msg("0.2.0", 2),
+                    register_msg("0.3.0", 3),
+                ],
+            },
+            &[],
+        )
+        .unwrap();
+    let wasm = response.events.iter().find(|e| e.ty == "wasm").unwrap();
+    assert_eq!(
+        wasm.attributes
+            .iter()
+            .filter(|a| a.key == "code_id")
+            .map(|a| a.value.as_str())
+            .collect::<Vec<_>>(),
+        vec!["1", "2", "3"]
+    );
+    let response = query_list_registrations(&mut app, contract.clone(), name.to_string()).unwrap();
+    assert_eq!(response.registrations.len(), 3);
+
+    let unregister_msg = |version: &str, code_id: u64| UnregisterMsg {
+        contract_name: name.to_string(),
+        chain_id: CHAIN_ID.to_string(),
+        code_id,
+        version: version.to_string(),
+    };
+
+    // Version and code ID mismatch, so nothing is unregistered.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(ADMIN_ADDR),
+            contract.clone(),
+            &ExecuteMsg::UnregisterBatch {
+                registrations: vec![unregister_msg("0.1.0", 1), unregister_msg("0.2.0", 3)],
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::BatchEntry {
+            index: 1,
+            error: Box::new(ContractError::NotFound {}),
+        }
+    );
+    let response = query_list_registrations(&mut app, contract.clone(), name.to_string()).unwrap();
+    assert_eq!(response.registrations.len(), 3);
+
+    app.execute_contract(
+        Addr::unchecked(ADMIN_ADDR),
+        contract.clone(),
+        &ExecuteMsg::UnregisterBatch {
+            registrations: vec![unregister_msg("0.1.0", 1), unregister_msg("0.2.0", 2)],
+        },
+        &[],
+    )
+    .unwrap();
+    let response = query_list_registrations(&mut app, contract, name.to_string()).unwrap();
+    assert_eq!(
+        response
+            .registrations
+            .into_iter()
+            .map(|r| r.code_id)
+            .collect::<Vec<_>>(),
+        vec![3]
+    );
+}
+
+#[test]
+fn test_batch_queries() {
+    let mut app = setup_app();
+    let contract = setup_test_case(&mut app);
+
+    for (name, version, code_id) in [("a", "0.1.0", 1), ("a", "0.2.0", 2), ("b", "1.0.0", 3)] {
+        register(
+            &mut app,
+            contract.clone(),
+            name.to_string(),
+            version.to_string(),
+            code_id,
+            Addr::unchecked(ADMIN_ADDR),
+        )
+        .unwrap();
+    }
+
+    let request = |name: &str, version: Option<&str>| RegistrationRequest {
+        name: name.to_string(),
+        chain_id: CHAIN_ID.to_string(),
+        version: version.map(str::to_string),
+    };
+    let response: GetRegistrationsResponse = app
+        .wrap()
+        .query_wasm_smart(
+            contract.clone(),
+            &QueryMsg::GetRegistrations {
+                requests: vec![
+                    request("a", None),
+                    request("a", Some("0.1.0")),
+                    request("a", Some("0.3.0")),
+                    request("c", None),
+                    request("b", None),
+                ],
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        response
+            .registrations
+            .iter()
+            .map(|r| r.as_ref().map(|r| r.code_id))
+            .collect::<Vec<_>>(),
+        vec![Some(2), Some(1), None, None, Some(3)]
+    );
+
+    let response: GetRegistrationsResponse = app
+        .wrap()
+        .query_wasm_smart(
+            contract,
+            &QueryMsg::GetCodeIdInfos {
+                chain_id: CHAIN_ID.to_string(),
+                code_ids: vec![3, 4, 1],
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        response
+            .registrations
+            .iter()
+            .map(|r| r.as_ref().map(|r| r.contract_name.as_str()))
+            .collect::<Vec<_>>(),
+        vec![Some("b"), None, Some("a")]
+    );
+}
+
+#[test]
+fn test_instantiate_by_name() {
+    let mut app = setup_app();
+    let contract = setup_test_case(&mut app);
+    let chain_id = app.block_info().chain_id;
+    let name: &str = "cw-code-id-registry";
+
+    // Register two versions of the registry itself on the local chain.
+    add_chain(
+        &mut app,
+        contract.clone(),
+        chain_id.clone(),
+        true,
+        Addr::unchecked(ADMIN_ADDR),
+    )
+    .unwrap();
+    let code_id1 = app.store_code(registry_contract());
+    let code_id2 = app.store_code(registry_contract());
+    for (version, code_id) in [("0.1.0", code_id1), ("0.2.0", code_id2)] {
+        app.execute_contract(
+            Addr::unchecked(ADMIN_ADDR),
+            contract.clone(),
+            &ExecuteMsg::Register {
+                contract_name: name.to_string(),
+                version: version.to_string(),
+                chain_id: chain_id.clone(),
+                code_id,
+                checksum: version.to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+    }
+
+    let instantiate_by_name =
+        |version_req: Option<&str>, funds: Vec<Coin>| ExecuteMsg::InstantiateByName {
+            name: name.to_string(),
+            version_req: version_req.map(str::to_string),
+            msg: to_json_binary(&InstantiateMsg {
+                admin: USER_ADDR.to_string(),
+            })
+            .unwrap(),
+            label: "registry".to_string(),
+            admin: None,
+            funds,
+        };
+
+    // Funds must match those sent.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(USER_ADDR),
+            contract.clone(),
+            &instantiate_by_name(None, coins(10, "ujuno")),
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::IncorrectPaymentAmount {});
+
+    // No version matches.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(USER_ADDR),
+            contract.clone(),
+            &instantiate_by_name(Some("^1"), vec![]),
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::NotFound {});
+
+    // Latest.
+    let response = app
+        .execute_contract(
+            Addr::unchecked(USER_ADDR),
+            contract.clone(),
+            &instantiate_by_name(None, coins(10, "ujuno")),
+            &coins(10, "ujuno"),
+        )
+        .unwrap();
+    let data: InstantiateByNameResponse = from_json(response.data.unwrap()).unwrap();
+    assert_eq!(data.registration.version, "0.2.0");
+    assert_eq!(
+        app.contract_data(&data.contract_address).unwrap().code_id,
+        code_id2 as usize
+    );
+    assert_eq!(
+        query_admin(&mut app, data.contract_address),
+        Addr::unchecked(USER_ADDR)
+    );
+
+    // Matching version requirement.
+    let response = app
+        .execute_contract(
+            Addr::unchecked(USER_ADDR),
+            contract,
+            &instantiate_by_name(Some("~0.1"), vec![]),
+            &[],
+        )
+        .unwrap();
+    let data: InstantiateByNameResponse = from_json(response.data.unwrap()).unwrap();
+    assert_eq!(data.registration.version, "0.1.0");
+    assert_eq!(
+        app.contract_data(&data.contract_address).unwrap().code_id,
+        code_id1 as usize
+    );
+}
+
+#[test]
+fn test_nested_instantiate_by_name() {
+    let mut app = setup_app();
+    let contract = setup_test_case(&mut app);
+    let chain_id = app.block_info().chain_id;
+    let admin = Addr::unchecked(ADMIN_ADDR);
+
+    add_chain(
+        &mut app,
+        contract.clone(),
+        chain_id.clone(),
+        true,
+        admin.clone(),
+    )
+    .unwrap();
+    let factory_code_id = app.store_code(factory_contract());
+    let child_code_id = app.store_code(migratable_contract());
+    for (name, code_id) in [
+        ("cw-factory", factory_code_id),
+        ("cw-migratable", child_code_id),
+    ] {
+        app.execute_contract(
+            admin.clone(),
+            contract.clone(),
+            &ExecuteMsg::Register {
+                contract_name: name.to_string(),
+                version: "1.0.0".to_string(),
+                chain_id: chain_id.clone(),
+                code_id,
+                checksum: "ab".repeat(32),
+            },
+            &[],
+        )
+        .unwrap();
+    }
+
+    // The factory instantiates its child by name while being instantiated
+    // by name, so both instantiations are pending at once.
+    let response = app
+        .execute_contract(
+            Addr::unchecked(USER_ADDR),
+            contract.clone(),
+            &ExecuteMsg::InstantiateByName {
+                name: "cw-factory".to_string(),
+                version_req: None,
+                msg: to_json_binary(&contract).unwrap(),
+                label: "factory".to_string(),
+                admin: None,
+                funds: vec![],
+            },
+            &[],
+        )
+        .unwrap();
+    let data: InstantiateByNameResponse = from_json(response.data.unwrap()).unwrap();
+    assert_eq!(data.registration.contract_name, "cw-factory");
+
+    let instances = |code_id: u64| -> ListInstancesResponse {
+        app.wrap()
+            .query_wasm_smart(
+                contract.clone(),
+                &QueryMsg::ListInstances {
+                    chain_id: chain_id.clone(),
+                    code_id,
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap()
+    };
+    let factories = instances(factory_code_id).instances;
+    assert_eq!(factories.len(), 1);
+    assert_eq!(factories[0].address, data.contract_address);
+    assert_eq!(
+        factories[0].instantiated_by,
+        Some(Addr::unchecked(USER_ADDR))
+    );
+    let children = instances(child_code_id).instances;
+    assert_eq!(children.len(), 1);
+    assert_eq!(children[0].instantiated_by, Some(data.contract_address));
+}
+
+/// An Api using bech32 addresses, as MockApi can not humanize the 32 byte
+/// addresses instantiate2 gives.
+struct Bech32Api(&'static str);
+
+impl Api for Bech32Api {
+    fn addr_validate(&self, human: &str) -> StdResult<Addr> {
+        self.addr_humanize(&self.addr_canonicalize(human)?)
+    }
+
+    fn addr_canonicalize(&self, human: &str) -> StdResult<CanonicalAddr> {
+        match bech32::decode(human) {
+            Ok((prefix, data, Variant::Bech32)) if prefix == self.0 => {
+                Ok(Vec::<u8>::from_base32(&data)
+                    .map_err(|e| StdError::generic_err(e.to_string()))?
+                    .into())
+            }
+            _ => Err(StdError::generic_err(format!("Invalid address {}", human))),
+        }
+    }
+
+    fn addr_humanize(&self, canonical: &CanonicalAddr) -> StdResult<Addr> {
+        bech32::encode(self.0, canonical.as_slice().to_base32(), Variant::Bech32)
+            .map(Addr::unchecked)
+            .map_err(|e| StdError::generic_err(e.to_string()))
+    }
+
+    fn secp256k1_verify(
+        &self,
+        message_hash: &[u8],
+        signature: &[u8],
+        public_key: &[u8],
+    ) -> Result<bool, VerificationError> {
+        MockApi::default().secp256k1_verify(message_hash, signature, public_key)
+    }
+
+    fn secp256k1_recover_pubkey(
+        &self,
+        message_hash: &[u8],
+        signature: &[u8],
+        recovery_param: u8,
+    ) -> Result<Vec<u8>, RecoverPubkeyError> {
+        MockApi::default().secp256k1_recover_pubkey(message_hash, signature, recovery_param)
+    }
+
+    fn ed25519_verify(
+        &self,
+        message: &[u8],
+        signature: &[u8],
+        public_key: &[u8],
+    ) -> Result<bool, VerificationError> {
+        MockApi::default().ed25519_verify(message, signature, public_key)
+    }
+
+    fn ed25519_batch_verify(
+        &self,
+        messages: &[&[u8]],
+        signatures: &[&[u8]],
+        public_keys: &[&[u8]],
+    ) -> Result<bool, VerificationError> {
+        MockApi::default().ed25519_batch_verify(messages, signatures, public_keys)
+    }
+
+    fn debug(&self, message: &str) {
+        MockApi::default().debug(message)
+    }
+}
+
+#[test]
+fn test_instantiate2_by_name() {
+    // Test vector from wasmd's instantiate2 address tests.
+    let creator: &str = "juno1nxvenxve42424242hwamhwamenxvenxv0ngyvf";
+    let expected: &str = "juno1t6r960j945lfv8mhl4mage2rg97w63xeynwrupum2s2l7em4lprsgj0ke2";
+    let admin: &str = "juno1qyqszqgpqyqszqgpqyqszqgpqyqszqgpypz92q";
+    let mut deps = OwnedDeps {
+        storage: MockStorage::default(),
+        api: Bech32Api("juno"),
+        querier: MockQuerier::default(),
+        custom_query_type: PhantomData::<Empty>,
+    };
+    let mut env = mock_env();
+    env.contract.address = Addr::unchecked(creator);
+    let name: &str = "cw-core";
+    let version: &str = "1.0.0";
+    let checksum: &str = "13a1fc994cc6d1c81b746ee0c0ff6f90043875e0bf1d9be6b7d779fc978dc2a5";
+    let salt = Binary::from(b"a".to_vec());
+
+    crate::contract::instantiate(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(admin, &[]),
+        InstantiateMsg {
+            admin: admin.to_string(),
+        },
+    )
+    .unwrap();
+    for msg in [
+        ExecuteMsg::AddChain {
+            chain_id: env.block.chain_id.clone(),
+            bech32_prefix: "juno".to_string(),
+            pretty_name: "Juno".to_string(),
+            is_testnet: false,
+        },
+        ExecuteMsg::Register {
+            contract_name: name.to_string(),
+            version: version.to_string(),
+            chain_id: env.block.chain_id.clone(),
+            code_id: 7,
+            checksum: checksum.to_string(),
+        },
+    ] {
+        crate::contract::execute(deps.as_mut(), env.clone(), mock_info(admin, &[]), msg).unwrap();
+    }
+
+    let predicted: PredictAddressResponse = from_json(
+        crate::contract::query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::PredictAddress {
+                name: name.to_string(),
+                version: version.to_string(),
+                creator: creator.to_string(),
+                salt: salt.clone(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(predicted.address, expected);
+    assert_eq!(predicted.registration.code_id, 7);
+
+    // A different salt gives a different address.
+    let other: PredictAddressResponse = from_json(
+        crate::contract::query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::PredictAddress {
+                name: name.to_string(),
+                version: version.to_string(),
+                creator: creator.to_string(),
+                salt: Binary::from(b"other".to_vec()),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_ne!(predicted.address, other.address);
+
+    let instantiate2_by_name = |deps: DepsMut, salt: Binary| {
+        crate::contract::execute(
+            deps,
+            env.clone(),
+            mock_info(USER_ADDR, &[]),
+            ExecuteMsg::Instantiate2ByName {
+                name: name.to_string(),
+                version: version.to_string(),
+                msg: Binary::from(b"{}".to_vec()),
+                label: "core".to_string(),
+                admin: None,
+                funds: vec![],
+                salt,
+            },
+        )
+        .unwrap()
+    };
+    let response = instantiate2_by_name(deps.as_mut(), salt.clone());
+    assert_eq!(
+        response.messages[0].msg,
+        CosmosMsg::Wasm(WasmMsg::Instantiate2 {
+            admin: None,
+            code_id: 7,
+            label: "core".to_string(),
+            msg: Binary::from(b"{}".to_vec()),
+            funds: vec![],
+            salt,
+        })
+    );
+    assert!(response
+        .attributes
+        .iter()
+        .any(|a| a.key == "predicted_address" && a.value == predicted.address.as_str()));
+
+    // An instantiation started while another is pending replies first, and
+    // both are recorded.
+    let nested = instantiate2_by_name(deps.as_mut(), Binary::from(b"other".to_vec()));
+    let reply = |deps: DepsMut, id: u64, address: &Addr| {
+        // A MsgInstantiateContractResponse with only its address set.
+        let mut data = vec![0x0a, address.as_str().len() as u8];
+        data.extend_from_slice(address.as_bytes());
+        crate::contract::reply(
+            deps,
+            env.clone(),
+            Reply {
+                id,
+                result: SubMsgResult::Ok(SubMsgResponse {
+                    events: vec![],
+                    data: Some(Binary::from(data)),
+                }),
+            },
+        )
+        .unwrap()
+    };
+    assert_ne!(nested.messages[0].id, response.messages[0].id);
+    reply(deps.as_mut(), nested.messages[0].id, &other.address);
+    reply(deps.as_mut(), response.messages[0].id, &predicted.address);
+    let instances: ListInstancesResponse = from_json(
+        crate::contract::query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::ListInstances {
+                chain_id: env.block.chain_id.clone(),
+                code_id: 7,
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    let mut addresses: Vec<Addr> = instances
+        .instances
+        .into_iter()
+        .map(|instance| instance.address)
+        .collect();
+    addresses.sort();
+    let mut predicted_addresses = vec![predicted.address, other.address];
+    predicted_addresses.sort();
+    assert_eq!(addresses, predicted_addresses);
+}
+
+#[test]
+fn test_instances() {
+    let mut app = setup_app();
+    let contract = setup_test_case(&mut app);
+    let chain_id = app.block_info().chain_id;
+    let name: &str = "cw-code-id-registry";
+
+    add_chain(
+        &mut app,
+        contract.clone(),
+        chain_id.clone(),
+        true,
+        Addr::unchecked(ADMIN_ADDR),
+    )
+    .unwrap();
+    let code_id = app.store_code(registry_contract());
+    let unregistered_code_id = app.store_code(registry_contract());
+    app.execute_contract(
+        Addr::unchecked(ADMIN_ADDR),
+        contract.clone(),
+        &ExecuteMsg::Register {
+            contract_name: name.to_string(),
+            version: "0.1.0".to_string(),
+            chain_id: chain_id.clone(),
+            code_id,
+            checksum: "checksum".to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Instances created by the registry are recorded.
+    let response = app
+        .execute_contract(
+            Addr::unchecked(USER_ADDR),
+            contract.clone(),
+            &ExecuteMsg::InstantiateByName {
+                name: name.to_string(),
+                version_req: None,
+                msg: to_json_binary(&InstantiateMsg {
+                    admin: USER_ADDR.to_string(),
+                })
+                .unwrap(),
+                label: "registry".to_string(),
+                admin: None,
+                funds: vec![],
+            },
+            &[],
+        )
+        .unwrap();
+    let created: InstantiateByNameResponse = from_json(response.data.unwrap()).unwrap();
+
+    // Instances created elsewhere may be reported.
+    let instantiate = |app: &mut App, code_id: u64| {
+        app.instantiate_contract(
+            code_id,
+            Addr::unchecked(OTHER_USER_ADDR),
+            &InstantiateMsg {
+                admin: OTHER_USER_ADDR.to_string(),
+            },
+            &[],
+            "registry",
+            None,
+        )
+        .unwrap()
+    };
+    let reported = instantiate(&mut app, code_id);
+    app.execute_contract(
+        Addr::unchecked(OTHER_USER_ADDR),
+        contract.clone(),
+        &ExecuteMsg::ReportInstance {
+            address: reported.to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Instances of unregistered code may not be reported.
+    let unregistered = instantiate(&mut app, unregistered_code_id);
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(OTHER_USER_ADDR),
+            contract.clone(),
+            &ExecuteMsg::ReportInstance {
+                address: unregistered.to_string(),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::NotFound {});
+
+    let response: ListInstancesResponse = app
+        .wrap()
+        .query_wasm_smart(
+            contract.clone(),
+            &QueryMsg::ListInstances {
+                chain_id: chain_id.clone(),
+                code_id,
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    let mut expected = vec![
+        InstanceInfo {
+            address: created.contract_address.clone(),
+            chain_id: chain_id.clone(),
+            code_id,
+            instantiated_by: Some(Addr::unchecked(USER_ADDR)),
+        },
+        InstanceInfo {
+            address: reported.clone(),
+            chain_id: chain_id.clone(),
+            code_id,
+            instantiated_by: None,
+        },
+    ];
+    expected.sort_by(|a, b| a.address.cmp(&b.address));
+    assert_eq!(response.instances, expected);
+
+    // Paginate.
+    let response: ListInstancesResponse = app
+        .wrap()
+        .query_wasm_smart(
+            contract.clone(),
+            &QueryMsg::ListInstances {
+                chain_id,
+                code_id,
+                start_after: Some(expected[0].address.to_string()),
+                limit: Some(1),
+            },
+        )
+        .unwrap();
+    assert_eq!(response.instances, vec![expected[1].clone()]);
+
+    let response: GetRegistrationResponse = app
+        .wrap()
+        .query_wasm_smart(
+            contract.clone(),
+            &QueryMsg::GetInstanceRegistration {
+                address: reported.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(response.registration.contract_name, name);
+    assert_eq!(response.registration.code_id, code_id);
+
+    let err = app
+        .wrap()
+        .query_wasm_smart::<GetRegistrationResponse>(
+            contract,
+            &QueryMsg::GetInstanceRegistration {
+                address: unregistered.to_string(),
+            },
+        )
+        .unwrap_err();
+    assert!(err
+        .to_string()
+        .contains(&ContractError::NotFound {}.to_string()));
+}
+
+#[test]
+fn test_upgrade_path() {
+    let mut app = setup_app();
+    let contract = setup_test_case(&mut app);
+    let name: &str = "name";
+
+    for (code_id, version) in [(1, "0.1.0"), (2, "0.2.0"), (3, "1.0.0"), (4, "2.0.0")] {
+        register(
+            &mut app,
+            contract.clone(),
+            name.to_string(),
+            version.to_string(),
+            code_id,
+            Addr::unchecked(ADMIN_ADDR),
+        )
+        .unwrap();
+    }
+
+    let add_edge = |app: &mut App, from: &str, to: &str, breaking: bool, sender: &str| {
+        app.execute_contract(
+            Addr::unchecked(sender),
+            contract.clone(),
+            &ExecuteMsg::AddMigrationEdge {
+                contract_name: name.to_string(),
+                from_version: from.to_string(),
+                to_version: to.to_string(),
+                migrate_msg: Some("{\"from_compatible\":{}}".to_string()),
+                breaking,
+            },
+            &[],
+        )
+    };
+
+    // Only those who may register may add edges.
+    let err: ContractError = add_edge(&mut app, "0.1.0", "0.2.0", false, USER_ADDR)
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::UnauthorizedRegistration {});
+
+    // Edges must go forward.
+    let err: ContractError = add_edge(&mut app, "0.2.0", "0.1.0", false, ADMIN_ADDR)
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::InvalidMigrationEdge("0.2.0".to_string(), "0.1.0".to_string())
+    );
+
+    // 0.1.0 -> 0.2.0 -> 1.0.0 -> 2.0.0, with a shortcut 0.1.0 -> 1.0.0 and
+    // an edge to a version not registered on this chain.
+    add_edge(&mut app, "0.1.0", "0.2.0", false, ADMIN_ADDR).unwrap();
+    add_edge(&mut app, "0.2.0", "1.0.0", true, ADMIN_ADDR).unwrap();
+    add_edge(&mut app, "0.1.0", "1.0.0", true, ADMIN_ADDR).unwrap();
+    add_edge(&mut app, "1.0.0", "2.0.0", true, ADMIN_ADDR).unwrap();
+    add_edge(&mut app, "2.0.0", "3.0.0", false, ADMIN_ADDR).unwrap();
+
+    let upgrade_path = |app: &mut App, code_id: u64| -> UpgradePathResponse {
+        app.wrap()
+            .query_wasm_smart(
+                contract.clone(),
+                &QueryMsg::UpgradePath {
+                    chain_id: CHAIN_ID.to_string(),
+                    code_id,
+                },
+            )
+            .unwrap()
+    };
+    let versions = |response: &UpgradePathResponse| {
+        response
+            .path
+            .iter()
+            .map(|step| step.registration.version.as_str())
+            .collect::<Vec<_>>()
+            .join(",")
+    };
+
+    let response = upgrade_path(&mut app, 1);
+    assert_eq!(response.current.version, "0.1.0");
+    assert_eq!(versions(&response), "1.0.0,2.0.0");
+    assert_eq!(response.path[0].edge.from_version, "0.1.0");
+    assert!(response.path[0].edge.breaking);
+
+    let response = upgrade_path(&mut app, 2);
+    assert_eq!(versions(&response), "1.0.0,2.0.0");
+
+    // Nothing newer reachable.
+    let response = upgrade_path(&mut app, 4);
+    assert!(response.path.is_empty());
+
+    // Removing the last edge cuts off 2.0.0.
+    app.execute_contract(
+        Addr::unchecked(ADMIN_ADDR),
+        contract.clone(),
+        &ExecuteMsg::RemoveMigrationEdge {
+            contract_name: name.to_string(),
+            from_version: "1.0.0".to_string(),
+            to_version: "2.0.0".to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+    let response = upgrade_path(&mut app, 1);
+    assert_eq!(versions(&response), "1.0.0");
+}
+
+#[test]
+fn test_migrate_to_version() {
+    let mut app = setup_app();
+    let contract = setup_test_case(&mut app);
+    let chain_id = app.block_info().chain_id;
+
+    add_chain(
+        &mut app,
+        contract.clone(),
+        chain_id.clone(),
+        true,
+        Addr::unchecked(ADMIN_ADDR),
+    )
+    .unwrap();
+    let mut code_ids = vec![];
+    for (name, version) in [
+        ("voting", "0.1.0"),
+        ("voting", "0.2.0"),
+        ("proposal", "0.1.0"),
+    ] {
+        let code_id = app.store_code(migratable_contract());
+        app.execute_contract(
+            Addr::unchecked(ADMIN_ADDR),
+            contract.clone(),
+            &ExecuteMsg::Register {
+                contract_name: name.to_string(),
+                version: version.to_string(),
+                chain_id: chain_id.clone(),
+                code_id,
+                checksum: "checksum".to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+        code_ids.push(code_id);
+    }
+
+    // Instantiate with the registry as admin.
+    let response = app
+        .execute_contract(
+            Addr::unchecked(USER_ADDR),
+            contract.clone(),
+            &ExecuteMsg::InstantiateByName {
+                name: "voting".to_string(),
+                version_req: Some("=0.1.0".to_string()),
+                msg: to_json_binary(&Empty {}).unwrap(),
+                label: "voting".to_string(),
+                admin: Some(contract.to_string()),
+                funds: vec![],
+            },
+            &[],
+        )
+        .unwrap();
+    let voting = from_json::<InstantiateByNameResponse>(response.data.unwrap())
+        .unwrap()
+        .contract_address;
+
+    let migrate = |app: &mut App, name: &str, version: &str, sender: &str| {
+        app.execute_contract(
+            Addr::unchecked(sender),
+            contract.clone(),
+            &ExecuteMsg::MigrateToVersion {
+                contract_addr: voting.to_string(),
+                name: name.to_string(),
+                version: version.to_string(),
+                msg: to_json_binary(&Empty {}).unwrap(),
+            },
+            &[],
+        )
+    };
+
+    // Only admin or the instantiator may migrate.
+    let err: ContractError = migrate(&mut app, "voting", "0.2.0", OTHER_USER_ADDR)
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::UnauthorizedMigration {});
+
+    // Can't migrate a voting module onto proposal code.
+    let err: ContractError = migrate(&mut app, "proposal", "0.1.0", USER_ADDR)
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::MigrationNameMismatch(
+            voting.to_string(),
+            "voting".to_string(),
+            "proposal".to_string()
+        )
+    );
+
+    migrate(&mut app, "voting", "0.2.0", USER_ADDR).unwrap();
+
+    // Contracts can't be migrated back onto older code.
+    let err: ContractError = migrate(&mut app, "voting", "0.1.0", USER_ADDR)
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::MigrationNotNewer(
+            voting.to_string(),
+            "0.2.0".to_string(),
+            "0.1.0".to_string()
+        )
+    );
+    assert_eq!(
+        app.contract_data(&voting).unwrap().code_id,
+        code_ids[1] as usize
+    );
+
+    // The recorded instance follows the migration.
+    let response: ListInstancesResponse = app
+        .wrap()
+        .query_wasm_smart(
+            contract.clone(),
+            &QueryMsg::ListInstances {
+                chain_id,
+                code_id: code_ids[1],
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(response.instances.len(), 1);
+    assert_eq!(response.instances[0].address, voting);
+
+    // Contracts the registry is not admin of can't be migrated.
+    let other = app
+        .instantiate_contract(
+            code_ids[0],
+            Addr::unchecked(USER_ADDR),
+            &Empty {},
+            &[],
+            "voting",
+            Some(USER_ADDR.to_string()),
+        )
+        .unwrap();
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(ADMIN_ADDR),
+            contract,
+            &ExecuteMsg::MigrateToVersion {
+                contract_addr: other.to_string(),
+                name: "voting".to_string(),
+                version: "0.2.0".to_string(),
+                msg: to_json_binary(&Empty {}).unwrap(),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::RegistryNotContractAdmin(other.to_string())
+    );
+}
+
+#[test]
+fn test_resolve_dependencies() {
+    let mut app = setup_app();
+    let contract = setup_test_case(&mut app);
+
+    let mut code_id = 0;
+    for (name, version) in [
+        ("core", "1.0.0"),
+        ("core", "2.0.0"),
+        ("proposal", "1.1.0"),
+        ("proposal", "1.2.0"),
+        ("voting", "1.0.0"),
+        ("voting", "1.1.0"),
+        ("voting", "2.0.0"),
+    ] {
+        code_id += 1;
+        register(
+            &mut app,
+            contract.clone(),
+            name.to_string(),
+            version.to_string(),
+            code_id,
+            Addr::unchecked(ADMIN_ADDR),
+        )
+        .unwrap();
+    }
+
+    let set_dependencies = |app: &mut App, name: &str, version: &str, deps: &[(&str, &str)]| {
+        app.execute_contract(
+            Addr::unchecked(ADMIN_ADDR),
+            contract.clone(),
+            &ExecuteMsg::SetDependencies {
+                contract_name: name.to_string(),
+                version: version.to_string(),
+                dependencies: deps
+                    .iter()
+                    .map(|(name, version_req)| Dependency {
+                        contract_name: name.to_string(),
+                        version_req: version_req.to_string(),
+                    })
+                    .collect(),
+            },
+            &[],
+        )
+    };
+
+    // Requirements must be valid.
+    let err: ContractError = set_dependencies(&mut app, "core", "1.0.0", &[("voting", "one")])
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::InvalidVersionReq(req, _) if req == "one"));
+
+    // The newest proposal needs voting 2, which core 1 doesn't allow, so
+    // the resolver falls back to the older proposal.
+    set_dependencies(
+        &mut app,
+        "core",
+        "1.0.0",
+        &[("proposal", "^1"), ("voting", "^1")],
+    )
+    .unwrap();
+    set_dependencies(&mut app, "proposal", "1.1.0", &[("voting", ">=1.1")]).unwrap();
+    set_dependencies(&mut app, "proposal", "1.2.0", &[("voting", "^2")]).unwrap();
+    set_dependencies(&mut app, "core", "2.0.0", &[("missing", "^1")]).unwrap();
+
+    let response: GetDependenciesResponse = app
+        .wrap()
+        .query_wasm_smart(
+            contract.clone(),
+            &QueryMsg::GetDependencies {
+                name: "proposal".to_string(),
+                version: "1.2.0".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(response.dependencies.len(), 1);
+
+    let response: ResolveDependenciesResponse = app
+        .wrap()
+        .query_wasm_smart(
+            contract.clone(),
+            &QueryMsg::ResolveDependencies {
+                name: "core".to_string(),
+                version: "1.0.0".to_string(),
+                chain_id: CHAIN_ID.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        response
+            .registrations
+            .iter()
+            .map(|r| format!("{} {}", r.contract_name, r.version))
+            .collect::<Vec<_>>(),
+        vec!["core 1.0.0", "proposal 1.1.0", "voting 1.1.0"]
+    );
+
+    let err = app
+        .wrap()
+        .query_wasm_smart::<ResolveDependenciesResponse>(
+            contract,
+            &QueryMsg::ResolveDependencies {
+                name: "core".to_string(),
+                version: "2.0.0".to_string(),
+                chain_id: CHAIN_ID.to_string(),
+            },
+        )
+        .unwrap_err();
+    assert!(err.to_string().contains(
+        &ContractError::UnsatisfiableDependency {
+            contract_name: "missing".to_string(),
+            version_req: "^1".to_string(),
+            required_by: "core 2.0.0".to_string(),
+            chain_id: CHAIN_ID.to_string(),
+        }
+        .to_string()
+    ));
+}
+
+#[test]
+fn test_interfaces() {
+    let mut app = setup_app();
+    let contract = setup_test_case(&mut app);
+
+    for (code_id, name, version) in [
+        (1, "cw20-staked", "1.0.0"),
+        (2, "cw20-staked", "2.0.0"),
+        (3, "cw4-voting", "1.0.0"),
+        (4, "cw-core", "1.0.0"),
+    ] {
+        register(
+            &mut app,
+            contract.clone(),
+            name.to_string(),
+            version.to_string(),
+            code_id,
+            Addr::unchecked(ADMIN_ADDR),
+        )
+        .unwrap();
+    }
+
+    let set_interfaces = |app: &mut App, name: &str, version: &str, interfaces: &[&str]| {
+        app.execute_contract(
+            Addr::unchecked(ADMIN_ADDR),
+            contract.clone(),
+            &ExecuteMsg::SetInterfaces {
+                contract_name: name.to_string(),
+                version: version.to_string(),
+                interfaces: interfaces
+                    .iter()
+                    .map(|name| Interface {
+                        name: name.to_string(),
+                        version: "1.0.0".to_string(),
+                    })
+                    .collect(),
+            },
+            &[],
+        )
+    };
+    let list_by_interface = |app: &mut App,
+                             interface: &str,
+                             start_after: Option<(&str, &str)>,
+                             limit: Option<u32>|
+     -> Vec<String> {
+        let response: ListByInterfaceResponse = app
+            .wrap()
+            .query_wasm_smart(
+                contract.clone(),
+                &QueryMsg::ListByInterface {
+                    interface: interface.to_string(),
+                    chain_id: CHAIN_ID.to_string(),
+                    start_after: start_after
+                        .map(|(name, version)| (name.to_string(), version.to_string())),
+                    limit,
+                },
+            )
+            .unwrap();
+        response
+            .implementations
+            .into_iter()
+            .map(|i| {
+                format!(
+                    "{} {}",
+                    i.registration.contract_name, i.registration.version
+                )
+            })
+            .collect()
+    };
+
+    set_interfaces(&mut app, "cw20-staked", "1.0.0", &["dao-voting-module"]).unwrap();
+    set_interfaces(
+        &mut app,
+        "cw20-staked",
+        "2.0.0",
+        &["dao-voting-module", "cw20-hooks"],
+    )
+    .unwrap();
+    set_interfaces(&mut app, "cw4-voting", "1.0.0", &["dao-voting-module"]).unwrap();
+    set_interfaces(&mut app, "cw-core", "1.0.0", &["dao-core"]).unwrap();
+    // Versions not registered on the chain are not listed.
+    set_interfaces(&mut app, "cw4-voting", "9.0.0", &["dao-voting-module"]).unwrap();
+
+    assert_eq!(
+        list_by_interface(&mut app, "dao-voting-module", None, None),
+        vec!["cw4-voting 1.0.0", "cw20-staked 1.0.0", "cw20-staked 2.0.0"]
+    );
+    assert_eq!(
+        list_by_interface(
+            &mut app,
+            "dao-voting-module",
+            Some(("cw20-staked", "1.0.0")),
+            Some(1)
+        ),
+        vec!["cw20-staked 2.0.0"]
+    );
+    assert_eq!(
+        list_by_interface(&mut app, "cw20-hooks", None, None),
+        vec!["cw20-staked 2.0.0"]
+    );
+
+    // Replacing interfaces updates the index.
+    set_interfaces(&mut app, "cw20-staked", "1.0.0", &[]).unwrap();
+    assert_eq!(
+        list_by_interface(&mut app, "dao-voting-module", None, None),
+        vec!["cw4-voting 1.0.0", "cw20-staked 2.0.0"]
+    );
+
+    let response: GetInterfacesResponse = app
+        .wrap()
+        .query_wasm_smart(
+            contract.clone(),
+            &QueryMsg::GetInterfaces {
+                name: "cw20-staked".to_string(),
+                version: "2.0.0".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(response.interfaces.len(), 2);
+
+    // Only those who may register may set interfaces.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(USER_ADDR),
+            contract,
+            &ExecuteMsg::SetInterfaces {
+                contract_name: "cw-core".to_string(),
+                version: "1.0.0".to_string(),
+                interfaces: vec![],
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::UnauthorizedRegistration {});
+}
+
+#[test]
+fn test_schemas() {
+    let mut app = setup_app();
+    let contract = setup_test_case(&mut app);
+
+    let schemas = MessageSchemas {
+        instantiate_msg: Some(
+            "a1b2c3d4e5f60718293a4b5c6d7e8f90a1b2c3d4e5f60718293a4b5c6d7e8f90".to_string(),
+        ),
+        execute_msg: None,
+        query_msg: None,
+        migrate_msg: None,
+        uri: Some("ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi".to_string()),
+    };
+
+    // Nothing is stored until schemas are set.
+    let err = app
+        .wrap()
+        .query_wasm_smart::<GetSchemasResponse>(
+            contract.clone(),
+            &QueryMsg::GetSchemas {
+                name: "cw-core".to_string(),
+                version: "1.0.0".to_string(),
+            },
+        )
+        .unwrap_err();
+    assert!(err.to_string().contains("Contract not found"));
+
+    app.execute_contract(
+        Addr::unchecked(ADMIN_ADDR),
+        contract.clone(),
+        &ExecuteMsg::SetSchemas {
+            contract_name: "cw-core".to_string(),
+            version: "1.0.0".to_string(),
+            schemas: schemas.clone(),
+        },
+        &[],
+    )
+    .unwrap();
+    let response: GetSchemasResponse = app
+        .wrap()
+        .query_wasm_smart(
+            contract.clone(),
+            &QueryMsg::GetSchemas {
+                name: "cw-core".to_string(),
+                version: "1.0.0".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(response.schemas, schemas);
+
+    // Hashes must be hex encoded SHA-256 hashes.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(ADMIN_ADDR),
+            contract.clone(),
+            &ExecuteMsg::SetSchemas {
+                contract_name: "cw-core".to_string(),
+                version: "1.0.0".to_string(),
+                schemas: MessageSchemas {
+                    query_msg: Some("not-a-hash".to_string()),
+                    ..schemas.clone()
+                },
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::InvalidSchemaHash("not-a-hash".to_string())
+    );
+
+    // Only those who may register may set schemas.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(USER_ADDR),
+            contract,
+            &ExecuteMsg::SetSchemas {
+                contract_name: "cw-core".to_string(),
+                version: "1.0.0".to_string(),
+                schemas,
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::UnauthorizedRegistration {});
+}
+
+#[test]
+fn test_ibc_sync() {
+    let env = mock_env();
+    let chain_id = env.block.chain_id.clone();
+    let setup = |mut deps: DepsMut| {
+        crate::contract::instantiate(
+            deps.branch(),
+            env.clone(),
+            mock_info(ADMIN_ADDR, &[]),
+            InstantiateMsg {
+                admin: ADMIN_ADDR.to_string(),
+            },
+        )
+        .unwrap();
+        crate::contract::execute(
+            deps,
+            env.clone(),
+            mock_info(ADMIN_ADDR, &[]),
+            ExecuteMsg::AddSyncPeer {
+                connection_id: "connection-2".to_string(),
+                port_id: "their_port".to_string(),
+            },
+        )
+        .unwrap();
+    };
+    let register_msg = RegisterMsg {
+        contract_name: "cw-core".to_string(),
+        version: "1.0.0".to_string(),
+        chain_id: chain_id.clone(),
+        code_id: 1,
+        checksum: "".to_string(),
+    };
+
+    // Only approved peers may open channels.
+    let mut canonical = mock_dependencies();
+    let err = ibc_channel_open(
+        canonical.as_mut(),
+        env.clone(),
+        mock_ibc_channel_open_try("channel-0", IbcOrder::Unordered, IBC_VERSION),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::UnapprovedSyncPeer("connection-2".to_string(), "their_port".to_string())
+    );
+
+    setup(canonical.as_mut());
+    let err = crate::contract::execute(
+        canonical.as_mut(),
+        env.clone(),
+        mock_info(USER_ADDR, &[]),
+        ExecuteMsg::AddSyncPeer {
+            connection_id: "connection-2".to_string(),
+            port_id: "their_port".to_string(),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::UnauthorizedSyncPeer {});
+
+    // Channels must be unordered and speak the registry's version.
+    let err = ibc_channel_open(
+        canonical.as_mut(),
+        env.clone(),
+        mock_ibc_channel_open_try("channel-0", IbcOrder::Ordered, IBC_VERSION),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::OrderedChannel {});
+    let err = ibc_channel_open(
+        canonical.as_mut(),
+        env.clone(),
+        mock_ibc_channel_open_try("channel-0", IbcOrder::Unordered, "ics20-1"),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidIbcVersion("ics20-1".to_string(), IBC_VERSION.to_string())
+    );
+
+    // Registrations on the canonical registry are pushed to connected channels.
+    ibc_channel_connect(
+        canonical.as_mut(),
+        env.clone(),
+        mock_ibc_channel_connect_ack("channel-0", IbcOrder::Unordered, IBC_VERSION),
+    )
+    .unwrap();
+    crate::contract::execute(
+        canonical.as_mut(),
+        env.clone(),
+        mock_info(ADMIN_ADDR, &[]),
+        ExecuteMsg::AddChain {
+            chain_id: chain_id.clone(),
+            bech32_prefix: "juno".to_string(),
+            pretty_name: "Juno".to_string(),
+            is_testnet: false,
+        },
+    )
+    .unwrap();
+    let response = crate::contract::execute(
+        canonical.as_mut(),
+        env.clone(),
+        mock_info(ADMIN_ADDR, &[]),
+        ExecuteMsg::RegisterBatch {
+            registrations: vec![register_msg.clone()],
+        },
+    )
+    .unwrap();
+    match &response.messages[..] {
+        [SubMsg {
+            msg:
+                CosmosMsg::Ibc(IbcMsg::SendPacket {
+                    channel_id, data, ..
+                }),
+            ..
+        }] => {
+            assert_eq!(channel_id, "channel-0");
+            assert_eq!(
+                from_json::<SyncPacket>(data).unwrap(),
+                SyncPacket {
+                    sequence: 1,
+                    update: RegistryPacket::Register(register_msg.clone())
+                }
+            );
+        }
+        messages => panic!("unexpected messages {:?}", messages),
+    }
+
+    // Updates the follower rejects or that time out are kept and resent in
+    // order.
+    let sync_packet = |update: RegistryPacket, sequence: u64| SyncPacket { sequence, update };
+    let rejected = sync_packet(RegistryPacket::Register(register_msg.clone()), 1);
+    ibc_packet_ack(
+        canonical.as_mut(),
+        env.clone(),
+        mock_ibc_packet_ack(
+            "channel-0",
+            &rejected,
+            IbcAcknowledgement::new(
+                to_json_binary(&RegistryAck::Error("Registry is frozen".to_string())).unwrap(),
+            ),
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    let timed_out = sync_packet(
+        RegistryPacket::Unregister(UnregisterMsg {
+            contract_name: "cw-core".to_string(),
+            chain_id: chain_id.clone(),
+            code_id: 1,
+            version: "1.0.0".to_string(),
+        }),
+        2,
+    );
+    ibc_packet_timeout(
+        canonical.as_mut(),
+        env.clone(),
+        mock_ibc_packet_timeout("channel-0", &timed_out).unwrap(),
+    )
+    .unwrap();
+    let response: ListUndeliveredUpdatesResponse = from_json(
+        crate::contract::query(
+            canonical.as_ref(),
+            env.clone(),
+            QueryMsg::ListUndeliveredUpdates {
+                channel_id: "channel-0".to_string(),
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(response.updates, vec![rejected.clone(), timed_out.clone()]);
+
+    let err = crate::contract::execute(
+        canonical.as_mut(),
+        env.clone(),
+        mock_info(USER_ADDR, &[]),
+        ExecuteMsg::Resync {
+            channel_id: "channel-0".to_string(),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::UnauthorizedResync {});
+    let response = crate::contract::execute(
+        canonical.as_mut(),
+        env.clone(),
+        mock_info(ADMIN_ADDR, &[]),
+        ExecuteMsg::Resync {
+            channel_id: "channel-0".to_string(),
+        },
+    )
+    .unwrap();
+    let resent: Vec<SyncPacket> = response
+        .messages
+        .iter()
+        .map(|message| match &message.msg {
+            CosmosMsg::Ibc(IbcMsg::SendPacket { data, .. }) => from_json(data).unwrap(),
+            msg => panic!("unexpected message {:?}", msg),
+        })
+        .collect();
+    assert_eq!(resent, vec![rejected, timed_out]);
+    let response: ListUndeliveredUpdatesResponse = from_json(
+        crate::contract::query(
+            canonical.as_ref(),
+            env.clone(),
+            QueryMsg::ListUndeliveredUpdates {
+                channel_id: "channel-0".to_string(),
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert!(response.updates.is_empty());
+
+    // The admin may stop syncing to a channel.
+    let response = crate::contract::execute(
+        canonical.as_mut(),
+        env.clone(),
+        mock_info(ADMIN_ADDR, &[]),
+        ExecuteMsg::CloseSyncChannel {
+            channel_id: "channel-0".to_string(),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        response.messages,
+        vec![SubMsg::new(IbcMsg::CloseChannel {
+            channel_id: "channel-0".to_string()
+        })]
+    );
+    let response: SyncChannelsResponse = from_json(
+        crate::contract::query(canonical.as_ref(), env.clone(), QueryMsg::SyncChannels {}).unwrap(),
+    )
+    .unwrap();
+    assert!(response.channels.is_empty());
+
+    // Followers only accept packets from their trusted channel, and don't
+    // need the chain in their catalog.
+    let mut follower = mock_dependencies();
+    setup(follower.as_mut());
+    let receive = |deps: DepsMut, channel_id: &str, packet: SyncPacket| -> RegistryAck {
+        let response = ibc_packet_receive(
+            deps,
+            env.clone(),
+            mock_ibc_packet_recv(channel_id, &packet).unwrap(),
+        )
+        .unwrap();
+        from_json(response.acknowledgement).unwrap()
+    };
+    assert_eq!(
+        receive(
+            follower.as_mut(),
+            "channel-7",
+            sync_packet(RegistryPacket::Register(register_msg.clone()), 1)
+        ),
+        RegistryAck::Error(ContractError::UntrustedChannel("channel-7".to_string()).to_string())
+    );
+
+    let err = crate::contract::execute(
+        follower.as_mut(),
+        env.clone(),
+        mock_info(ADMIN_ADDR, &[]),
+        ExecuteMsg::SetTrustedChannel {
+            channel_id: Some("channel-7".to_string()),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::UnknownChannel("channel-7".to_string()));
+    ibc_channel_connect(
+        follower.as_mut(),
+        env.clone(),
+        mock_ibc_channel_connect_confirm("channel-7", IbcOrder::Unordered, IBC_VERSION),
+    )
+    .unwrap();
+    let err = crate::contract::execute(
+        follower.as_mut(),
+        env.clone(),
+        mock_info(USER_ADDR, &[]),
+        ExecuteMsg::SetTrustedChannel {
+            channel_id: Some("channel-7".to_string()),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::UnauthorizedSetTrustedChannel {});
+    crate::contract::execute(
+        follower.as_mut(),
+        env.clone(),
+        mock_info(ADMIN_ADDR, &[]),
+        ExecuteMsg::SetTrustedChannel {
+            channel_id: Some("channel-7".to_string()),
+        },
+    )
+    .unwrap();
+
+    // Updates must arrive in sequence.
+    assert_eq!(
+        receive(
+            follower.as_mut(),
+            "channel-7",
+            sync_packet(RegistryPacket::Register(register_msg.clone()), 2)
+        ),
+        RegistryAck::Error(
+            ContractError::UnexpectedSequence {
+                expected: 1,
+                sequence: 2
+            }
+            .to_string()
+        )
+    );
+    assert_eq!(
+        receive(
+            follower.as_mut(),
+            "channel-7",
+            sync_packet(RegistryPacket::Register(register_msg.clone()), 1)
+        ),
+        RegistryAck::Result(Binary::default())
+    );
+    let response: GetRegistrationResponse = from_json(
+        crate::contract::query(
+            follower.as_ref(),
+            env.clone(),
+            QueryMsg::GetCodeIdInfo {
+                chain_id: chain_id.clone(),
+                code_id: 1,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(response.registration.contract_name, "cw-core");
+
+    // Followers reject local updates.
+    let err = crate::contract::execute(
+        follower.as_mut(),
+        env.clone(),
+        mock_info(ADMIN_ADDR, &[]),
+        ExecuteMsg::Unregister {
+            contract_name: "cw-core".to_string(),
+            chain_id: chain_id.clone(),
+            code_id: 1,
+            version: "1.0.0".to_string(),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::FollowerRegistry("channel-7".to_string())
+    );
+
+    assert_eq!(
+        receive(
+            follower.as_mut(),
+            "channel-7",
+            sync_packet(
+                RegistryPacket::Unregister(UnregisterMsg {
+                    contract_name: "cw-core".to_string(),
+                    chain_id: chain_id.clone(),
+                    code_id: 1,
+                    version: "1.0.0".to_string(),
+                }),
+                2
+            )
+        ),
+        RegistryAck::Result(Binary::default())
+    );
+
+    let response: SyncChannelsResponse = from_json(
+        crate::contract::query(follower.as_ref(), env, QueryMsg::SyncChannels {}).unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        response,
+        SyncChannelsResponse {
+            trusted_channel: Some("channel-7".to_string()),
+            channels: vec!["channel-7".to_string()],
+            peers: vec![SyncPeer {
+                connection_id: "connection-2".to_string(),
+                port_id: "their_port".to_string(),
+            }],
+        }
+    );
+}
+
+#[test]
+fn test_import_signed() {
+    use k256::ecdsa::signature::Signer as _;
+
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+    crate::contract::instantiate(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(ADMIN_ADDR, &[]),
+        InstantiateMsg {
+            admin: ADMIN_ADDR.to_string(),
+        },
+    )
+    .unwrap();
+
+    let secp256k1_key = k256::ecdsa::SigningKey::from_slice(&[1; 32]).unwrap();
+    let secp256k1_public_key = Binary::from(
+        secp256k1_key
+            .verifying_key()
+            .to_encoded_point(true)
+            .as_bytes(),
+    );
+    let ed25519_key = ed25519_zebra::SigningKey::from([2; 32]);
+    let ed25519_public_key =
+        Binary::from(<[u8; 32]>::from(ed25519_zebra::VerificationKey::from(&ed25519_key)).to_vec());
+    let sign = |payload: &Binary| -> Vec<PayloadSignature> {
+        let secp256k1_signature: k256::ecdsa::Signature = secp256k1_key.sign(payload);
+        vec![
+            PayloadSignature {
+                public_key: secp256k1_public_key.clone(),
+                signature: Binary::from(secp256k1_signature.to_bytes().to_vec()),
+            },
+            PayloadSignature {
+                public_key: ed25519_public_key.clone(),
+                signature: Binary::from(
+                    <[u8; 64]>::from(ed25519_key.sign(payload.as_slice())).to_vec(),
+                ),
+            },
+        ]
+    };
+    let register_msg = |code_id: u64| RegisterMsg {
+        contract_name: "cw-core".to_string(),
+        version: format!("{}.0.0", code_id),
+        chain_id: CHAIN_ID.to_string(),
+        code_id,
+        checksum: "".to_string(),
+    };
+    let signed_payload = |nonce: u64, msg: RegisterMsg| SignedPayload {
+        chain_id: env.block.chain_id.clone(),
+        registry: env.contract.address.to_string(),
+        nonce,
+        updates: vec![RegistryPacket::Register(msg)],
+    };
+    let payload = |nonce: u64, code_id: u64| -> Binary {
+        to_json_binary(&signed_payload(nonce, register_msg(code_id))).unwrap()
+    };
+    let import = |deps: DepsMut, payload: Binary, signatures: Vec<PayloadSignature>| {
+        crate::contract::execute(
+            deps,
+            env.clone(),
+            mock_info(USER_ADDR, &[]),
+            ExecuteMsg::ImportSigned {
+                payload,
+                signatures,
+            },
+        )
+    };
+
+    let err = import(deps.as_mut(), payload(0, 1), sign(&payload(0, 1))).unwrap_err();
+    assert_eq!(err, ContractError::NoSigners {});
+
+    let signers = vec![
+        Signer {
+            key_type: KeyType::Secp256k1,
+            public_key: secp256k1_public_key.clone(),
+        },
+        Signer {
+            key_type: KeyType::Ed25519,
+            public_key: ed25519_public_key.clone(),
+        },
+    ];
+    let set_signers = |deps: DepsMut, sender: &str, threshold: u32| {
+        crate::contract::execute(
+            deps,
+            env.clone(),
+            mock_info(sender, &[]),
+            ExecuteMsg::SetSigners {
+                signers: signers.clone(),
+                threshold,
+            },
+        )
+    };
+    let err = set_signers(deps.as_mut(), USER_ADDR, 2).unwrap_err();
+    assert_eq!(err, ContractError::UnauthorizedSetSigners {});
+    let err = set_signers(deps.as_mut(), ADMIN_ADDR, 3).unwrap_err();
+    assert_eq!(err, ContractError::InvalidSignerThreshold(3, 2));
+    set_signers(deps.as_mut(), ADMIN_ADDR, 2).unwrap();
+
+    // Payloads are only valid on the registry they were signed for.
+    let other_registry = to_json_binary(&SignedPayload {
+        registry: "other-registry".to_string(),
+        ..signed_payload(0, register_msg(1))
+    })
+    .unwrap();
+    let err = import(deps.as_mut(), other_registry.clone(), sign(&other_registry)).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::WrongRegistry {
+            chain_id: env.block.chain_id.clone(),
+            registry: "other-registry".to_string()
+        }
+    );
+
+    // Registrations are validated like those of the admin.
+    let unknown_chain = to_json_binary(&signed_payload(0, register_msg(1))).unwrap();
+    let err = import(deps.as_mut(), unknown_chain.clone(), sign(&unknown_chain)).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::BatchEntry {
+            index: 0,
+            error: Box::new(ContractError::UnknownChain(CHAIN_ID.to_string()))
+        }
+    );
+    crate::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(ADMIN_ADDR, &[]),
+        ExecuteMsg::AddChain {
+            chain_id: CHAIN_ID.to_string(),
+            bech32_prefix: "juno".to_string(),
+            pretty_name: "Juno".to_string(),
+            is_testnet: false,
+        },
+    )
+    .unwrap();
+    let invalid_name = to_json_binary(&signed_payload(
+        0,
+        RegisterMsg {
+            contract_name: "CW Core".to_string(),
+            ..register_msg(1)
+        },
+    ))
+    .unwrap();
+    let err = import(deps.as_mut(), invalid_name.clone(), sign(&invalid_name)).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::BatchEntry {
+            index: 0,
+            error: Box::new(ContractError::InvalidContractNameChar(
+                "CW Core".to_string(),
+                'C'
+            ))
+        }
+    );
+    let update_validation = |deps: DepsMut, validation: ValidationRules| {
+        crate::contract::execute(
+            deps,
+            env.clone(),
+            mock_info(ADMIN_ADDR, &[]),
+            ExecuteMsg::UpdateConfig {
+                registration_mode: None,
+                validation: Some(validation),
+                registration_fee: None,
+                rate_limit: None,
+            },
+        )
+        .unwrap()
+    };
+    update_validation(
+        deps.as_mut(),
+        ValidationRules {
+            require_checksum: true,
+            ..ValidationRules::default()
+        },
+    );
+    let err = import(deps.as_mut(), payload(0, 1), sign(&payload(0, 1))).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::BatchEntry {
+            index: 0,
+            error: Box::new(ContractError::InvalidChecksum("".to_string()))
+        }
+    );
+    update_validation(deps.as_mut(), ValidationRules::default());
+
+    // Signatures must be valid, from distinct signers, and meet the threshold.
+    let mut signatures = sign(&payload(0, 1));
+    signatures[1] = signatures[0].clone();
+    let err = import(deps.as_mut(), payload(0, 1), signatures).unwrap_err();
+    assert_eq!(err, ContractError::InsufficientSignatures(1, 2));
+    let err = import(deps.as_mut(), payload(0, 1), sign(&payload(0, 2))).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidSignature(hex::encode(secp256k1_public_key.as_slice()))
+    );
+
+    // Anyone may relay a signed payload, but only once and in order.
+    import(deps.as_mut(), payload(0, 1), sign(&payload(0, 1))).unwrap();
+    let err = import(deps.as_mut(), payload(0, 1), sign(&payload(0, 1))).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidNonce {
+            expected: 1,
+            nonce: 0
+        }
+    );
+    let err = import(deps.as_mut(), payload(2, 2), sign(&payload(2, 2))).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidNonce {
+            expected: 1,
+            nonce: 2
+        }
+    );
+    import(deps.as_mut(), payload(1, 2), sign(&payload(1, 2))).unwrap();
+
+    let response: GetRegistrationResponse = from_json(
+        crate::contract::query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::GetRegistration {
+                name: "cw-core".to_string(),
+                chain_id: CHAIN_ID.to_string(),
+                version: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(response.registration.code_id, 2);
+
+    let response: SignersResponse = from_json(
+        crate::contract::query(deps.as_ref(), env.clone(), QueryMsg::Signers {}).unwrap(),
+    )
+    .unwrap();
+    assert_eq!(response.next_nonce, 2);
+    assert_eq!(response.signer_set.unwrap().threshold, 2);
+
+    // Imported unregistrations wait out the timelock like the admin's.
+    crate::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(ADMIN_ADDR, &[]),
+        ExecuteMsg::SetTimelockDelay { blocks: 10 },
+    )
+    .unwrap();
+    let unregister_payload = |nonce: u64, code_id: u64| -> Binary {
+        to_json_binary(&SignedPayload {
+            updates: vec![RegistryPacket::Unregister(UnregisterMsg {
+                contract_name: "cw-core".to_string(),
+                chain_id: CHAIN_ID.to_string(),
+                code_id,
+                version: format!("{}.0.0", code_id),
+            })],
+            ..signed_payload(nonce, register_msg(code_id))
+        })
+        .unwrap()
+    };
+    let err = import(
+        deps.as_mut(),
+        unregister_payload(2, 3),
+        sign(&unregister_payload(2, 3)),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::BatchEntry {
+            index: 0,
+            error: Box::new(ContractError::NotFound {})
+        }
+    );
+    let response = import(
+        deps.as_mut(),
+        unregister_payload(2, 2),
+        sign(&unregister_payload(2, 2)),
+    )
+    .unwrap();
+    assert!(response
+        .attributes
+        .iter()
+        .any(|a| a.key == "action_id" && a.value == "0"));
+    let get_code_id_info = |deps: Deps| {
+        crate::contract::query(
+            deps,
+            env.clone(),
+            QueryMsg::GetCodeIdInfo {
+                chain_id: CHAIN_ID.to_string(),
+                code_id: 2,
+            },
+        )
+    };
+    get_code_id_info(deps.as_ref()).unwrap();
+    let mut later = env.clone();
+    later.block.height += 10;
+    crate::contract::execute(
+        deps.as_mut(),
+        later,
+        mock_info(USER_ADDR, &[]),
+        ExecuteMsg::ExecuteQueued { id: 0 },
+    )
+    .unwrap();
+    get_code_id_info(deps.as_ref()).unwrap_err();
+
+    // Followers only take updates from the registry they follow.
+    crate::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(ADMIN_ADDR, &[]),
+        ExecuteMsg::AddSyncPeer {
+            connection_id: "connection-2".to_string(),
+            port_id: "their_port".to_string(),
+        },
+    )
+    .unwrap();
+    ibc_channel_connect(
+        deps.as_mut(),
+        env.clone(),
+        mock_ibc_channel_connect_confirm("channel-7", IbcOrder::Unordered, IBC_VERSION),
+    )
+    .unwrap();
+    crate::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(ADMIN_ADDR, &[]),
+        ExecuteMsg::SetTrustedChannel {
+            channel_id: Some("channel-7".to_string()),
+        },
+    )
+    .unwrap();
+    let err = import(deps.as_mut(), payload(3, 3), sign(&payload(3, 3))).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::FollowerRegistry("channel-7".to_string())
+    );
+}
+
+#[test]
+fn test_pause_and_freeze() {
+    let mut app = setup_app();
+    let contract = setup_test_case(&mut app);
+    let admin = Addr::unchecked(ADMIN_ADDR);
+
+    let execute = |app: &mut App, sender: &Addr, msg: ExecuteMsg| -> Result<(), ContractError> {
+        app.execute_contract(sender.clone(), contract.clone(), &msg, &[])
+            .map(|_| ())
+            .map_err(|err| err.downcast().unwrap())
+    };
+    let query_pause_info = |app: &App| -> PauseInfoResponse {
+        app.wrap()
+            .query_wasm_smart(contract.clone(), &QueryMsg::PauseInfo {})
+            .unwrap()
+    };
+
+    register(
+        &mut app,
+        contract.clone(),
+        "cw-core".to_string(),
+        "1.0.0".to_string(),
+        1,
+        admin.clone(),
+    )
+    .unwrap();
+
+    let err = execute(
+        &mut app,
+        &Addr::unchecked(USER_ADDR),
+        ExecuteMsg::Pause { until: None },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::UnauthorizedPause {});
+
+    // Pausing until a height rejects writes until then, but not queries.
+    let until = Expiration::AtHeight(app.block_info().height + 10);
+    execute(&mut app, &admin, ExecuteMsg::Pause { until: Some(until) }).unwrap();
+    let err: ContractError = register(
+        &mut app,
+        contract.clone(),
+        "cw-core".to_string(),
+        "2.0.0".to_string(),
+        2,
+        admin.clone(),
+    )
+    .unwrap_err()
+    .downcast()
+    .unwrap();
+    assert_eq!(err, ContractError::Paused(until));
+    let err: ContractError = unregister(
+        &mut app,
+        contract.clone(),
+        "cw-core".to_string(),
+        "1.0.0".to_string(),
+        1,
+        admin.clone(),
+    )
+    .unwrap_err()
+    .downcast()
+    .unwrap();
+    assert_eq!(err, ContractError::Paused(until));
+    assert_eq!(
+        query_get_registration(&mut app, contract.clone(), "cw-core".to_string(), None)
+            .unwrap()
+            .registration
+            .code_id,
+        1
+    );
+    assert_eq!(
+        query_pause_info(&app),
+        PauseInfoResponse {
+            paused_until: Some(until),
+            frozen: false,
+        }
+    );
+
+    app.update_block(|block| block.height += 10);
+    assert_eq!(query_pause_info(&app).paused_until, None);
+    register(
+        &mut app,
+        contract.clone(),
+        "cw-core".to_string(),
+        "2.0.0".to_string(),
+        2,
+        admin.clone(),
+    )
+    .unwrap();
+
+    // Pausing indefinitely lasts until unpaused.
+    execute(&mut app, &admin, ExecuteMsg::Pause { until: None }).unwrap();
+    app.update_block(|block| block.height += 1000);
+    assert_eq!(
+        query_pause_info(&app).paused_until,
+        Some(Expiration::Never {})
+    );
+    execute(&mut app, &admin, ExecuteMsg::Unpause {}).unwrap();
+    assert_eq!(query_pause_info(&app).paused_until, None);
+
+    // Frozen registries are read-only for good.
+    execute(&mut app, &admin, ExecuteMsg::Freeze {}).unwrap();
+    for msg in [
+        ExecuteMsg::Unpause {},
+        ExecuteMsg::UpdateAdmin {
+            admin: USER_ADDR.to_string(),
+        },
+        ExecuteMsg::Unregister {
+            contract_name: "cw-core".to_string(),
+            chain_id: CHAIN_ID.to_string(),
+            code_id: 1,
+            version: "1.0.0".to_string(),
+        },
+    ] {
+        assert_eq!(
+            execute(&mut app, &admin, msg).unwrap_err(),
+            ContractError::Frozen {}
+        );
+    }
+    assert!(query_pause_info(&app).frozen);
+    assert_eq!(
+        query_list_registrations(&mut app, contract, "cw-core".to_string())
+            .unwrap()
+            .registrations
+            .len(),
+        2
+    );
+}
+
+#[test]
+fn test_timelock() {
+    let mut app = setup_app();
+    let contract = setup_test_case(&mut app);
+    let admin = Addr::unchecked(ADMIN_ADDR);
+    let user = Addr::unchecked(USER_ADDR);
+
+    let execute = |app: &mut App, sender: &Addr, msg: ExecuteMsg| -> Result<(), ContractError> {
+        app.execute_contract(sender.clone(), contract.clone(), &msg, &[])
+            .map(|_| ())
+            .map_err(|err| err.downcast().unwrap())
+    };
+    let list_queued_actions = |app: &App| -> Vec<QueuedAction> {
+        let response: ListQueuedActionsResponse = app
+            .wrap()
+            .query_wasm_smart(
+                contract.clone(),
+                &QueryMsg::ListQueuedActions {
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
+        response.actions
+    };
+
+    register(
+        &mut app,
+        contract.clone(),
+        "cw-core".to_string(),
+        "1.0.0".to_string(),
+        1,
+        admin.clone(),
+    )
+    .unwrap();
+
+    let err = execute(&mut app, &user, ExecuteMsg::SetTimelockDelay { blocks: 10 }).unwrap_err();
+    assert_eq!(err, ContractError::UnauthorizedSetTimelockDelay {});
+    let err = execute(
+        &mut app,
+        &admin,
+        ExecuteMsg::SetTimelockDelay { blocks: u64::MAX },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Overflow(OverflowError::new(
+            OverflowOperation::Add,
+            app.block_info().height,
+            u64::MAX
+        ))
+    );
+    execute(
+        &mut app,
+        &admin,
+        ExecuteMsg::SetTimelockDelay { blocks: 10 },
+    )
+    .unwrap();
+    let config: Config = app
+        .wrap()
+        .query_wasm_smart(contract.clone(), &QueryMsg::Config {})
+        .unwrap();
+    assert_eq!(config.timelock_delay, 10);
+
+    // Unregistrations are checked when queued and applied once executed.
+    let err: ContractError = unregister(
+        &mut app,
+        contract.clone(),
+        "cw-core".to_string(),
+        "2.0.0".to_string(),
+        1,
+        admin.clone(),
+    )
+    .unwrap_err()
+    .downcast()
+    .unwrap();
+    assert_eq!(err, ContractError::NotFound {});
+    unregister(
+        &mut app,
+        contract.clone(),
+        "cw-core".to_string(),
+        "1.0.0".to_string(),
+        1,
+        admin.clone(),
+    )
+    .unwrap();
+    let executable_at = app.block_info().height + 10;
+    assert_eq!(
+        list_queued_actions(&app),
+        vec![QueuedAction {
+            id: 0,
+            action: TimelockedAction::Unregister(UnregisterMsg {
+                contract_name: "cw-core".to_string(),
+                chain_id: CHAIN_ID.to_string(),
+                code_id: 1,
+                version: "1.0.0".to_string(),
+            }),
+            proposer: admin.clone(),
+            executable_at,
+        }]
+    );
+    assert!(
+        query_get_registration(&mut app, contract.clone(), "cw-core".to_string(), None).is_ok()
+    );
+
+    let err = execute(&mut app, &user, ExecuteMsg::ExecuteQueued { id: 0 }).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::ActionNotReady {
+            id: 0,
+            executable_at
+        }
+    );
+    app.update_block(|block| block.height += 10);
+    execute(&mut app, &user, ExecuteMsg::ExecuteQueued { id: 0 }).unwrap();
+    assert!(
+        query_get_registration(&mut app, contract.clone(), "cw-core".to_string(), None).is_err()
+    );
+    assert_eq!(list_queued_actions(&app), vec![]);
+
+    // Admin changes may be cancelled by the admin.
+    execute(
+        &mut app,
+        &admin,
+        ExecuteMsg::UpdateAdmin {
+            admin: USER_ADDR.to_string(),
+        },
+    )
+    .unwrap();
+    assert_eq!(query_admin(&mut app, contract.clone()), admin);
+    let err = execute(&mut app, &user, ExecuteMsg::CancelQueued { id: 1 }).unwrap_err();
+    assert_eq!(err, ContractError::UnauthorizedCancel {});
+    execute(&mut app, &admin, ExecuteMsg::CancelQueued { id: 1 }).unwrap();
+    app.update_block(|block| block.height += 10);
+    let err = execute(&mut app, &user, ExecuteMsg::ExecuteQueued { id: 1 }).unwrap_err();
+    assert_eq!(err, ContractError::NotFound {});
+
+    // Signers can import unregistrations, so they are timelocked too.
+    let signers = vec![Signer {
+        key_type: KeyType::Ed25519,
+        public_key: Binary::from(vec![1; 32]),
+    }];
+    execute(
+        &mut app,
+        &admin,
+        ExecuteMsg::SetSigners {
+            signers: signers.clone(),
+            threshold: 1,
+        },
+    )
+    .unwrap();
+    let query_signers = |app: &App| -> SignersResponse {
+        app.wrap()
+            .query_wasm_smart(contract.clone(), &QueryMsg::Signers {})
+            .unwrap()
+    };
+    assert_eq!(query_signers(&app).signer_set, None);
+    app.update_block(|block| block.height += 10);
+    execute(&mut app, &user, ExecuteMsg::ExecuteQueued { id: 2 }).unwrap();
+    assert_eq!(
+        query_signers(&app).signer_set,
+        Some(SignerSet {
+            signers,
+            threshold: 1
+        })
+    );
+
+    // The delay is itself timelocked.
+    execute(&mut app, &admin, ExecuteMsg::SetTimelockDelay { blocks: 0 }).unwrap();
+    app.update_block(|block| block.height += 10);
+    execute(&mut app, &user, ExecuteMsg::ExecuteQueued { id: 3 }).unwrap();
+    execute(
+        &mut app,
+        &admin,
+        ExecuteMsg::UpdateAdmin {
+            admin: USER_ADDR.to_string(),
+        },
+    )
+    .unwrap();
+    assert_eq!(query_admin(&mut app, contract), user);
+}
+
+#[test]
+fn test_guardian() {
+    let mut app = setup_app();
+    let contract = setup_test_case(&mut app);
+    let admin = Addr::unchecked(ADMIN_ADDR);
+    let user = Addr::unchecked(USER_ADDR);
+    let guardian = Addr::unchecked(OTHER_USER_ADDR);
+
+    let execute = |app: &mut App, sender: &Addr, msg: ExecuteMsg| -> Result<(), ContractError> {
+        app.execute_contract(sender.clone(), contract.clone(), &msg, &[])
+            .map(|_| ())
+            .map_err(|err| err.downcast().unwrap())
+    };
+
+    let group_code_id = app.store_code(cw4_group_contract());
+    let group = app
+        .instantiate_contract(
+            group_code_id,
+            admin.clone(),
+            &cw4_group::msg::InstantiateMsg {
+                admin: None,
+                members: vec![cw4::Member {
+                    addr: OTHER_USER_ADDR.to_string(),
+                    weight: 1,
+                }],
+            },
+            &[],
+            "guardians",
+            None,
+        )
+        .unwrap();
+
+    // Only the admin may set the guardian, which must be a cw4 group if
+    // claimed to be one.
+    let err = execute(
+        &mut app,
+        &user,
+        ExecuteMsg::SetGuardian {
+            guardian: Some(UncheckedGuardian::Address(USER_ADDR.to_string())),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::UnauthorizedSetGuardian {});
+    assert!(app
+        .execute_contract(
+            admin.clone(),
+            contract.clone(),
+            &ExecuteMsg::SetGuardian {
+                guardian: Some(UncheckedGuardian::Cw4Group(contract.to_string())),
+            },
+            &[],
+        )
+        .is_err());
+    execute(
+        &mut app,
+        &admin,
+        ExecuteMsg::SetGuardian {
+            guardian: Some(UncheckedGuardian::Cw4Group(group.to_string())),
+        },
+    )
+    .unwrap();
+    let config: Config = app
+        .wrap()
+        .query_wasm_smart(contract.clone(), &QueryMsg::Config {})
+        .unwrap();
+    assert_eq!(config.guardian, Some(Guardian::Cw4Group(group)));
+
+    // Group members may veto queued actions, but others may not.
+    execute(
+        &mut app,
+        &admin,
+        ExecuteMsg::SetTimelockDelay { blocks: 10 },
+    )
+    .unwrap();
+    execute(
+        &mut app,
+        &admin,
+        ExecuteMsg::UpdateAdmin {
+            admin: USER_ADDR.to_string(),
+        },
+    )
+    .unwrap();
+    let err = execute(&mut app, &user, ExecuteMsg::CancelQueued { id: 0 }).unwrap_err();
+    assert_eq!(err, ContractError::UnauthorizedCancel {});
+    execute(&mut app, &guardian, ExecuteMsg::CancelQueued { id: 0 }).unwrap();
+
+    // Removing the guardian is timelocked, but the guardian can't veto its
+    // own removal.
+    execute(&mut app, &admin, ExecuteMsg::SetGuardian { guardian: None }).unwrap();
+    let err = execute(&mut app, &guardian, ExecuteMsg::CancelQueued { id: 1 }).unwrap_err();
+    assert_eq!(err, ContractError::UnauthorizedCancelSetGuardian {});
+    execute(&mut app, &admin, ExecuteMsg::CancelQueued { id: 1 }).unwrap();
+
+    // The guardian may pause, but not unpause or publish.
+    let err = execute(&mut app, &user, ExecuteMsg::Pause { until: None }).unwrap_err();
+    assert_eq!(err, ContractError::UnauthorizedPause {});
+    execute(&mut app, &guardian, ExecuteMsg::Pause { until: None }).unwrap();
+    let err = execute(&mut app, &guardian, ExecuteMsg::Unpause {}).unwrap_err();
+    assert_eq!(err, ContractError::UnauthorizedPause {});
+
+    // Nor may it end the pause early by shortening it.
+    let err = execute(
+        &mut app,
+        &guardian,
+        ExecuteMsg::Pause {
+            until: Some(Expiration::AtHeight(0)),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::PauseNotExtended(Expiration::Never {}));
+    let until = Expiration::AtHeight(app.block_info().height + 10);
+    execute(&mut app, &admin, ExecuteMsg::Pause { until: Some(until) }).unwrap();
+    let at_time = Expiration::AtTime(app.block_info().time.plus_seconds(1));
+    let err = execute(
+        &mut app,
+        &guardian,
+        ExecuteMsg::Pause {
+            until: Some(at_time),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::PauseNotExtended(until));
+    execute(&mut app, &guardian, ExecuteMsg::Pause { until: None }).unwrap();
+    execute(&mut app, &admin, ExecuteMsg::Unpause {}).unwrap();
+    let err: ContractError = register(
+        &mut app,
+        contract,
+        "cw-core".to_string(),
+        "1.0.0".to_string(),
+        1,
+        guardian,
+    )
+    .unwrap_err()
+    .downcast()
+    .unwrap();
+    assert_eq!(err, ContractError::UnauthorizedRegistration {});
+}
+
+#[test]
+fn test_delegations() {
+    let mut app = setup_app();
+    let contract = setup_test_case(&mut app);
+    let admin = Addr::unchecked(ADMIN_ADDR);
+    let bot = Addr::unchecked(USER_ADDR);
+
+    let execute = |app: &mut App, sender: &Addr, msg: ExecuteMsg| -> Result<(), ContractError> {
+        app.execute_contract(sender.clone(), contract.clone(), &msg, &[])
+            .map(|_| ())
+            .map_err(|err| err.downcast().unwrap())
+    };
+    let delegate = |names: &[&str]| ExecuteMsg::Delegate {
+        delegate: USER_ADDR.to_string(),
+        names: names.iter().map(|name| name.to_string()).collect(),
+        max_registrations: 2,
+        period: 100,
+        expiration: None,
+    };
+    let register_as_bot = |app: &mut App, name: &str, code_id: u64| -> Result<(), ContractError> {
+        register(
+            app,
+            contract.clone(),
+            name.to_string(),
+            format!("{}.0.0", code_id),
+            code_id,
+            bot.clone(),
+        )
+        .map(|_| ())
+        .map_err(|err| err.downcast().unwrap())
+    };
+
+    let err = execute(&mut app, &bot, delegate(&["cw-core"])).unwrap_err();
+    assert_eq!(err, ContractError::UnauthorizedDelegate {});
+    let err = execute(&mut app, &admin, delegate(&["dao/cw-core"])).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::NamespacedDelegation("dao/cw-core".to_string())
+    );
+    execute(&mut app, &admin, delegate(&["cw-core"])).unwrap();
+
+    // Delegates may register up to their limit of the names delegated to
+    // them each period.
+    let err = register_as_bot(&mut app, "cw20-base", 1).unwrap_err();
+    assert_eq!(err, ContractError::UnauthorizedRegistration {});
+    register_as_bot(&mut app, "cw-core", 1).unwrap();
+    register_as_bot(&mut app, "cw-core", 2).unwrap();
+    let err = register_as_bot(&mut app, "cw-core", 3).unwrap_err();
+    let retry_at = app.block_info().height + 100;
+    assert_eq!(err, ContractError::DelegationExhausted(retry_at));
+
+    app.update_block(|block| block.height += 100);
+    register_as_bot(&mut app, "cw-core", 3).unwrap();
+
+    let response: ListDelegationsResponse = app
+        .wrap()
+        .query_wasm_smart(
+            contract.clone(),
+            &QueryMsg::ListDelegations {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        response.delegations,
+        vec![Delegation {
+            delegate: bot.clone(),
+            names: vec!["cw-core".to_string()],
+            max_registrations: 2,
+            period: 100,
+            expiration: Expiration::Never {},
+            period_start: retry_at,
+            used: 1,
+        }]
+    );
+
+    // Delegates may not unregister.
+    let err: ContractError = unregister(
+        &mut app,
+        contract.clone(),
+        "cw-core".to_string(),
+        "1.0.0".to_string(),
+        1,
+        bot.clone(),
+    )
+    .unwrap_err()
+    .downcast()
+    .unwrap();
+    assert_eq!(err, ContractError::UnauthorizedRegistration {});
+
+    execute(
+        &mut app,
+        &admin,
+        ExecuteMsg::RevokeDelegation {
+            delegate: USER_ADDR.to_string(),
+        },
+    )
+    .unwrap();
+    let err = register_as_bot(&mut app, "cw-core", 4).unwrap_err();
+    assert_eq!(err, ContractError::UnauthorizedRegistration {});
+
+    // Periods may be as long as the admin likes.
+    execute(
+        &mut app,
+        &admin,
+        ExecuteMsg::Delegate {
+            delegate: USER_ADDR.to_string(),
+            names: vec!["cw-core".to_string()],
+            max_registrations: 1,
+            period: u64::MAX,
+            expiration: None,
+        },
+    )
+    .unwrap();
+    register_as_bot(&mut app, "cw-core", 4).unwrap();
+    let err = register_as_bot(&mut app, "cw-core", 5).unwrap_err();
+    assert_eq!(err, ContractError::DelegationExhausted(u64::MAX));
+
+    // Expired delegations are not listed or used.
+    let expiration = Expiration::AtHeight(app.block_info().height + 1);
+    execute(
+        &mut app,
+        &admin,
+        ExecuteMsg::Delegate {
+            delegate: USER_ADDR.to_string(),
+            names: vec!["cw-core".to_string()],
+            max_registrations: 2,
+            period: 100,
+            expiration: Some(expiration),
+        },
+    )
+    .unwrap();
+    app.update_block(|block| block.height += 1);
+    let err = register_as_bot(&mut app, "cw-core", 4).unwrap_err();
+    assert_eq!(err, ContractError::UnauthorizedRegistration {});
+    let response: ListDelegationsResponse = app
+        .wrap()
+        .query_wasm_smart(
+            contract,
+            &QueryMsg::ListDelegations {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(response.delegations, vec![]);
+}
+
+#[test]
+fn test_rate_limit() {
+    let mut app = setup_app();
+    let contract = setup_test_case(&mut app);
+    let admin = Addr::unchecked(ADMIN_ADDR);
+    let publisher = Addr::unchecked(USER_ADDR);
+
+    register_namespace(
+        &mut app,
+        contract.clone(),
+        "dao".to_string(),
+        USER_ADDR.to_string(),
+        admin.clone(),
+    )
+    .unwrap();
+
+    let rate_limit = RateLimit {
+        period: 10,
+        max_registrations: Some(3),
+        max_new_names: Some(1),
+    };
+    let set_rate_limit = |app: &mut App, sender: &Addr| -> AnyResult<AppResponse> {
+        app.execute_contract(
+            sender.clone(),
+            contract.clone(),
+            &ExecuteMsg::UpdateConfig {
+                registration_mode: None,
+                validation: None,
+                registration_fee: None,
+                rate_limit: Some(RateLimitUpdate::Set(rate_limit.clone())),
+            },
+            &[],
+        )
+    };
+    let err: ContractError = set_rate_limit(&mut app, &publisher)
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::UnauthorizedUpdateConfig {});
+    set_rate_limit(&mut app, &admin).unwrap();
+    let config: Config = app
+        .wrap()
+        .query_wasm_smart(contract.clone(), &QueryMsg::Config {})
+        .unwrap();
+    assert_eq!(config.rate_limit, Some(rate_limit.clone()));
+
+    let mut code_id = 0;
+    let mut publish = |app: &mut App, sender: &Addr, name: &str| -> Result<(), ContractError> {
+        code_id += 1;
+        register(
+            app,
+            contract.clone(),
+            name.to_string(),
+            format!("{}.0.0", code_id),
+            code_id,
+            sender.clone(),
+        )
+        .map(|_| ())
+        .map_err(|err| err.downcast().unwrap())
+    };
+    let retry_at = app.block_info().height + 10;
+
+    // Publishers may register one new name and three versions per period.
+    publish(&mut app, &publisher, "dao/core").unwrap();
+    publish(&mut app, &publisher, "dao/core").unwrap();
+    assert_eq!(
+        publish(&mut app, &publisher, "dao/voting").unwrap_err(),
+        ContractError::RateLimited { retry_at }
+    );
+    publish(&mut app, &publisher, "dao/core").unwrap();
+    assert_eq!(
+        publish(&mut app, &publisher, "dao/core").unwrap_err(),
+        ContractError::RateLimited { retry_at }
+    );
+
+    // The admin is not rate limited.
+    for _ in 0..4 {
+        publish(&mut app, &admin, "cw-core").unwrap();
+    }
+
+    app.update_block(|block| block.height += 10);
+    publish(&mut app, &publisher, "dao/voting").unwrap();
+
+    // Removing the rate limit lifts it.
+    let update_rate_limit = |app: &mut App, rate_limit: RateLimitUpdate| {
+        app.execute_contract(
+            admin.clone(),
+            contract.clone(),
+            &ExecuteMsg::UpdateConfig {
+                registration_mode: None,
+                validation: None,
+                registration_fee: None,
+                rate_limit: Some(rate_limit),
+            },
+            &[],
+        )
+        .unwrap()
+    };
+    update_rate_limit(&mut app, RateLimitUpdate::Remove {});
+    let config: Config = app
+        .wrap()
+        .query_wasm_smart(contract.clone(), &QueryMsg::Config {})
+        .unwrap();
+    assert_eq!(config.rate_limit, None);
+    for _ in 0..4 {
+        publish(&mut app, &publisher, "dao/voting").unwrap();
+    }
+
+    // Periods may be as long as the admin likes, though the publisher's
+    // current period then never ends.
+    update_rate_limit(
+        &mut app,
+        RateLimitUpdate::Set(RateLimit {
+            period: u64::MAX,
+            max_registrations: Some(1),
+            max_new_names: None,
+        }),
+    );
+    assert_eq!(
+        publish(&mut app, &publisher, "dao/voting").unwrap_err(),
+        ContractError::RateLimited { retry_at: u64::MAX }
+    );
+}
+
+#[test]
+fn test_config() {
+    let mut app = setup_app();
+    let contract = setup_test_case(&mut app);
+    let admin = Addr::unchecked(ADMIN_ADDR);
+    let user = Addr::unchecked(USER_ADDR);
+    let other_user = Addr::unchecked(OTHER_USER_ADDR);
+
+    let query_config = |app: &App| -> Config {
+        app.wrap()
+            .query_wasm_smart(contract.clone(), &QueryMsg::Config {})
+            .unwrap()
+    };
+    let update_config = |app: &mut App,
+                         sender: &Addr,
+                         registration_mode: Option<RegistrationMode>,
+                         validation: Option<ValidationRules>,
+                         registration_fee: Option<Vec<Coin>>|
+     -> Result<(), ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            contract.clone(),
+            &ExecuteMsg::UpdateConfig {
+                registration_mode,
+                validation,
+                registration_fee,
+                rate_limit: None,
+            },
+            &[],
+        )
+        .map(|_| ())
+        .map_err(|err| err.downcast().unwrap())
+    };
+    let publish = |app: &mut App,
+                   sender: &Addr,
+                   name: &str,
+                   version: &str,
+                   code_id: u64,
+                   funds: &[Coin]|
+     -> Result<AppResponse, ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            contract.clone(),
+            &ExecuteMsg::Register {
+                contract_name: name.to_string(),
+                version: version.to_string(),
+                chain_id: CHAIN_ID.to_string(),
+                code_id,
+                checksum: "ab".repeat(32),
+            },
+            funds,
+        )
+        .map_err(|err| err.downcast().unwrap())
+    };
+
+    assert_eq!(
+        query_config(&app),
+        Config {
+            admin: admin.clone(),
+            registration_mode: RegistrationMode::AdminOnly,
+            validation: ValidationRules::default(),
+            registration_fee: vec![],
+            paused_until: None,
+            frozen: false,
+            timelock_delay: 0,
+            guardian: None,
+            rate_limit: None,
+        }
+    );
+    assert_eq!(
+        update_config(&mut app, &user, Some(RegistrationMode::Open), None, None).unwrap_err(),
+        ContractError::UnauthorizedUpdateConfig {}
+    );
+
+    // Validation rules.
+    let strict = ValidationRules {
+        max_name_length: 8,
+        allow_prerelease: false,
+        require_checksum: true,
+    };
+    assert_eq!(
+        update_config(
+            &mut app,
+            &admin,
+            None,
+            Some(ValidationRules {
+                max_name_length: 65,
+                ..strict.clone()
+            }),
+            None
+        )
+        .unwrap_err(),
+        ContractError::InvalidMaxNameLength(64)
+    );
+    update_config(&mut app, &admin, None, Some(strict.clone()), None).unwrap();
+    assert_eq!(
+        publish(&mut app, &admin, "long-name", "1.0.0", 1, &[]).unwrap_err(),
+        ContractError::ContractNameTooLong("long-name".to_string(), 8)
+    );
+    assert_eq!(
+        publish(&mut app, &admin, "cw-core", "1.0.0-rc.1", 1, &[]).unwrap_err(),
+        ContractError::PrereleaseVersion("1.0.0-rc.1".to_string())
+    );
+    assert_eq!(
+        register(
+            &mut app,
+            contract.clone(),
+            "cw-core".to_string(),
+            "1.0.0".to_string(),
+            1,
+            admin.clone()
+        )
+        .unwrap_err()
+        .downcast::<ContractError>()
+        .unwrap(),
+        ContractError::InvalidChecksum("1.0.0".to_string())
+    );
+    publish(&mut app, &admin, "cw-core", "1.0.0", 1, &[]).unwrap();
+
+    // Partial updates leave the other fields alone.
+    update_config(&mut app, &admin, Some(RegistrationMode::Open), None, None).unwrap();
+    let config = query_config(&app);
+    assert_eq!(config.registration_mode, RegistrationMode::Open);
+    assert_eq!(config.validation, strict);
+    update_config(
+        &mut app,
+        &admin,
+        None,
+        Some(ValidationRules::default()),
+        Some(coins(10, "ujuno")),
+    )
+    .unwrap();
+
+    // Non-admins pay the registration fee to the admin.
+    assert_eq!(
+        publish(&mut app, &user, "cw-vote", "1.0.0", 2, &[]).unwrap_err(),
+        ContractError::IncorrectPaymentAmount {}
+    );
+    publish(&mut app, &user, "cw-vote", "1.0.0", 2, &coins(10, "ujuno")).unwrap();
+    assert_eq!(
+        app.wrap().query_balance(&admin, "ujuno").unwrap().amount,
+        Uint128::new(10010)
+    );
+    let err: ContractError = app
+        .execute_contract(
+            user.clone(),
+            contract.clone(),
+            &ExecuteMsg::RegisterBatch {
+                registrations: vec![],
+            },
+            &coins(10, "ujuno"),
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::IncorrectPaymentAmount {});
+
+    // In open mode the first publisher of a name owns it.
+    publish(&mut app, &user, "cw-vote", "1.1.0", 3, &coins(10, "ujuno")).unwrap();
+    assert_eq!(
+        publish(
+            &mut app,
+            &other_user,
+            "cw-vote",
+            "2.0.0",
+            4,
+            &coins(10, "ujuno")
+        )
+        .unwrap_err(),
+        ContractError::UnauthorizedRegistration {}
+    );
+    assert_eq!(
+        publish(
+            &mut app,
+            &other_user,
+            "cw-core",
+            "2.0.0",
+            4,
+            &coins(10, "ujuno")
+        )
+        .unwrap_err(),
+        ContractError::UnauthorizedRegistration {}
+    );
+    // Registrations with no fee due may not send funds.
+    assert_eq!(
+        publish(&mut app, &admin, "cw-vote", "2.0.0", 4, &coins(10, "ujuno")).unwrap_err(),
+        ContractError::Payment(PaymentError::NonPayable {})
+    );
+    update_config(&mut app, &admin, None, None, Some(vec![])).unwrap();
+    assert_eq!(
+        publish(&mut app, &user, "cw-vote", "2.0.0", 4, &coins(10, "ujuno")).unwrap_err(),
+        ContractError::Payment(PaymentError::NonPayable {})
+    );
+    publish(&mut app, &admin, "cw-vote", "2.0.0", 4, &[]).unwrap();
+
+    // In proposal mode registrations by others await the admin's approval,
+    // which holds their fee until then.
+    update_config(
+        &mut app,
+        &admin,
+        Some(RegistrationMode::Proposal),
+        None,
+        Some(coins(10, "ujuno")),
+    )
+    .unwrap();
+    let response = publish(
+        &mut app,
+        &other_user,
+        "cw-stake",
+        "1.0.0",
+        5,
+        &coins(10, "ujuno"),
+    )
+    .unwrap();
+    assert!(response.events.iter().any(|event| event
+        .attributes
+        .iter()
+        .any(|attr| attr.key == "proposal_id" && attr.value == "0")));
+    publish(
+        &mut app,
+        &other_user,
+        "cw-stake",
+        "1.1.0",
+        6,
+        &coins(10, "ujuno"),
+    )
+    .unwrap();
+    assert_eq!(
+        app.wrap().query_balance(&contract, "ujuno").unwrap().amount,
+        Uint128::new(20)
+    );
+    assert!(
+        query_get_registration(&mut app, contract.clone(), "cw-stake".to_string(), None).is_err()
+    );
+
+    let proposals: ListRegistrationProposalsResponse = app
+        .wrap()
+        .query_wasm_smart(
+            contract.clone(),
+            &QueryMsg::ListRegistrationProposals {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        proposals.proposals,
+        vec![
+            RegistrationProposal {
+                id: 0,
+                proposer: other_user.clone(),
+                registration: RegisterMsg {
+                    contract_name: "cw-stake".to_string(),
+                    version: "1.0.0".to_string(),
+                    chain_id: CHAIN_ID.to_string(),
+                    code_id: 5,
+                    checksum: "ab".repeat(32),
+                },
+                fee: coins(10, "ujuno"),
+            },
+            RegistrationProposal {
+                id: 1,
+                proposer: other_user.clone(),
+                registration: RegisterMsg {
+                    contract_name: "cw-stake".to_string(),
+                    version: "1.1.0".to_string(),
+                    chain_id: CHAIN_ID.to_string(),
+                    code_id: 6,
+                    checksum: "ab".repeat(32),
+                },
+                fee: coins(10, "ujuno"),
+            },
+        ]
+    );
+
+    let err: ContractError = app
+        .execute_contract(
+            other_user.clone(),
+            contract.clone(),
+            &ExecuteMsg::ApproveRegistration { id: 0 },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::UnauthorizedApproveRegistration {});
+    app.execute_contract(
+        admin.clone(),
+        contract.clone(),
+        &ExecuteMsg::ApproveRegistration { id: 0 },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        admin.clone(),
+        contract.clone(),
+        &ExecuteMsg::RejectRegistration { id: 1 },
+        &[],
+    )
+    .unwrap();
+
+    // The approved proposal's fee goes to the admin and the rejected one's
+    // is refunded.
+    assert_eq!(
+        app.wrap().query_balance(&contract, "ujuno").unwrap().amount,
+        Uint128::zero()
+    );
+    assert_eq!(
+        app.wrap().query_balance(&admin, "ujuno").unwrap().amount,
+        Uint128::new(10030)
+    );
+    assert_eq!(
+        app.wrap()
+            .query_balance(&other_user, "ujuno")
+            .unwrap()
+            .amount,
+        Uint128::new(9990)
+    );
+
+    let registration =
+        query_get_registration(&mut app, contract.clone(), "cw-stake".to_string(), None).unwrap();
+    assert_eq!(registration.registration.version, "1.0.0");
+    let list_proposals = |app: &App| -> Vec<RegistrationProposal> {
+        app.wrap()
+            .query_wasm_smart::<ListRegistrationProposalsResponse>(
+                contract.clone(),
+                &QueryMsg::ListRegistrationProposals {
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap()
+            .proposals
+    };
+    assert_eq!(list_proposals(&app), vec![]);
+
+    // Proposals may still be rejected while paused, and freezing rejects
+    // the rest, so their fees are always refunded.
+    for (version, code_id) in [("1.2.0", 7), ("1.3.0", 8)] {
+        publish(
+            &mut app,
+            &other_user,
+            "cw-stake",
+            version,
+            code_id,
+            &coins(10, "ujuno"),
+        )
+        .unwrap();
+    }
+    app.execute_contract(
+        admin.clone(),
+        contract.clone(),
+        &ExecuteMsg::Pause { until: None },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        admin.clone(),
+        contract.clone(),
+        &ExecuteMsg::RejectRegistration { id: 2 },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(admin, contract.clone(), &ExecuteMsg::Freeze {}, &[])
+        .unwrap();
+    assert_eq!(list_proposals(&app), vec![]);
+    assert_eq!(
+        app.wrap().query_balance(&contract, "ujuno").unwrap().amount,
+        Uint128::zero()
+    );
+    assert_eq!(
+        app.wrap()
+            .query_balance(&other_user, "ujuno")
+            .unwrap()
+            .amount,
+        Uint128::new(9990)
+    );
+}
+
+#[test]
+fn test_events() {
+    let mut app = setup_app();
+    let contract = setup_test_case(&mut app);
+    let admin = Addr::unchecked(ADMIN_ADDR);
+
+    let registration_event = |ty: &str| {
+        Event::new(format!("wasm-{}", ty))
+            .add_attribute("_contract_addr", contract.as_str())
+            .add_attribute("event_version", EVENT_VERSION)
+            .add_attribute("chain_id", CHAIN_ID)
+            .add_attribute("contract_name", "cw-core")
+            .add_attribute("version", "1.0.0")
+            .add_attribute("code_id", "1")
+            .add_attribute("checksum", "1.0.0")
+    };
+
+    let response = register(
+        &mut app,
+        contract.clone(),
+        "cw-core".to_string(),
+        "1.0.0".to_string(),
+        1,
+        admin.clone(),
+    )
+    .unwrap();
+    response.assert_event(&registration_event(REGISTER_EVENT));
+
+    let response = unregister(
+        &mut app,
+        contract.clone(),
+        "cw-core".to_string(),
+        "1.0.0".to_string(),
+        1,
+        admin.clone(),
+    )
+    .unwrap();
+    response.assert_event(&registration_event(UNREGISTER_EVENT));
+
+    // Empty checksums are left out, as attribute values may not be empty.
+    let response = app
+        .execute_contract(
+            admin.clone(),
+            contract.clone(),
+            &ExecuteMsg::Register {
+                contract_name: "cw-core".to_string(),
+                version: "1.0.0".to_string(),
+                chain_id: CHAIN_ID.to_string(),
+                code_id: 1,
+                checksum: "".to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+    let event = response
+        .events
+        .iter()
+        .find(|event| event.ty == format!("wasm-{}", REGISTER_EVENT))
+        .unwrap();
+    assert!(event.attributes.iter().all(|attr| attr.key != "checksum"));
+
+    let response = update_admin(&mut app, contract.clone(), USER_ADDR.to_string(), admin).unwrap();
+    response.assert_event(
+        &Event::new(format!("wasm-{}", UPDATE_ADMIN_EVENT))
+            .add_attribute("_contract_addr", contract.as_str())
+            .add_attribute("event_version", EVENT_VERSION)
+            .add_attribute("old_admin", ADMIN_ADDR)
+            .add_attribute("new_admin", USER_ADDR),
+    );
+}
+
+#[cfg(feature = "library")]
+#[test]
+fn test_helpers() {
+    use crate::helpers::CodeIdRegistryContract;
+
+    let mut app = setup_app();
+    let registry = CodeIdRegistryContract::new(setup_test_case(&mut app));
+    let admin = Addr::unchecked(ADMIN_ADDR);
+
+    let registration = RegisterMsg {
+        contract_name: "cw-core".to_string(),
+        version: "1.0.0".to_string(),
+        chain_id: CHAIN_ID.to_string(),
+        code_id: 1,
+        checksum: "1.0.0".to_string(),
+    };
+    app.execute(
+        admin.clone(),
+        registry.register(registration, vec![]).unwrap().into(),
+    )
+    .unwrap();
+
+    let querier = app.wrap();
+    assert_eq!(registry.admin(&querier).unwrap(), admin);
+    let expected = Registration {
+        contract_name: "cw-core".to_string(),
+        version: "1.0.0".to_string(),
+        code_id: 1,
+        checksum: "1.0.0".to_string(),
+    };
+    assert_eq!(
+        registry
+            .get_registration(&querier, "cw-core", CHAIN_ID, None)
+            .unwrap(),
+        Some(expected.clone())
+    );
+    assert_eq!(
+        registry
+            .get_registration(&querier, "cw-vote", CHAIN_ID, None)
+            .unwrap(),
+        None
+    );
+    assert_eq!(
+        registry.code_id_info(&querier, CHAIN_ID, 1).unwrap(),
+        Some(expected.clone())
+    );
+    assert_eq!(registry.code_id_info(&querier, CHAIN_ID, 2).unwrap(), None);
+    assert_eq!(
+        registry
+            .resolve(&querier, "cw-core", "1.0.0", CHAIN_ID)
+            .unwrap(),
+        vec![expected]
+    );
+
+    app.execute(admin, registry.update_admin(USER_ADDR).unwrap().into())
+        .unwrap();
+    assert_eq!(
+        registry.admin(&app.wrap()).unwrap(),
+        Addr::unchecked(USER_ADDR)
+    );
+}
+
+#[test]
+fn test_mock_registry() {
+    let mut app = setup_app();
+    let admin = Addr::unchecked(ADMIN_ADDR);
+
+    let fixture: Fixture = from_json(
+        br#"{
+            "chains": [
+                {
+                    "chain_id": "juno-1",
+                    "bech32_prefix": "juno",
+                    "pretty_name": "Juno",
+                    "is_testnet": false
+                }
+            ],
+            "registrations": [
+                {
+                    "contract_name": "cw-core",
+                    "version": "1.0.0",
+                    "chain_id": "juno-1",
+                    "code_id": 123,
+                    "checksum": "abc"
+                }
+            ]
+        }"#,
+    )
+    .unwrap();
+    let registry = MockRegistry::new(&mut app, &admin, fixture).unwrap();
+    let response: GetRegistrationResponse = app
+        .wrap()
+        .query_wasm_smart(
+            registry.addr.clone(),
+            &QueryMsg::GetRegistration {
+                name: "cw-core".to_string(),
+                chain_id: "juno-1".to_string(),
+                version: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        response.registration,
+        Registration {
+            contract_name: "cw-core".to_string(),
+            version: "1.0.0".to_string(),
+            code_id: 123,
+            checksum: "abc".to_string(),
+        }
+    );
+    let response: GetRegistrationsResponse = app
+        .wrap()
+        .query_wasm_smart(
+            registry.addr.clone(),
+            &QueryMsg::GetCodeIdInfos {
+                chain_id: "juno-1".to_string(),
+                code_ids: vec![123],
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        response.registrations,
+        vec![Some(Registration {
+            contract_name: "cw-core".to_string(),
+            version: "1.0.0".to_string(),
+            code_id: 123,
+            checksum: "abc".to_string(),
+        })]
+    );
+
+    // Code stored and registered on the app's chain resolves by name.
+    let code_id = registry
+        .store_and_register(&mut app, "cw-migratable", "0.1.0", migratable_contract())
+        .unwrap();
+    assert_eq!(
+        registry.code_id(&app, "cw-migratable", None).unwrap(),
+        code_id
+    );
+    assert!(registry
+        .code_id(&app, "cw-migratable", Some("0.2.0"))
+        .is_err());
+    app.execute_contract(
+        Addr::unchecked(USER_ADDR),
+        registry.addr.clone(),
+        &ExecuteMsg::InstantiateByName {
+            name: "cw-migratable".to_string(),
+            version_req: None,
+            msg: to_json_binary(&Empty {}).unwrap(),
+            label: "migratable".to_string(),
+            admin: None,
+            funds: vec![],
+        },
+        &[],
+    )
+    .unwrap();
+}
@@ -0,0 +1,110 @@
+use semver::{Version, VersionReq};
+
+use crate::error::ContractError;
+use crate::msg::RegisterMsg;
+use crate::state::ValidationRules;
+
+/// Maximum length of the namespace and of the name in a contract name.
+pub const MAX_NAME_SEGMENT_LENGTH: usize = 64;
+
+/// Contract names are lowercase `[a-z0-9-_]` strings, optionally
+/// namespaced as `org/name`.
+pub fn validate_contract_name(contract_name: &str) -> Result<(), ContractError> {
+    if contract_name.is_empty() {
+        return Err(ContractError::EmptyContractName {});
+    }
+
+    let segments: Vec<&str> = contract_name.split('/').collect();
+    if segments.len() > 2 || segments.iter().any(|segment| segment.is_empty()) {
+        return Err(ContractError::InvalidContractNamespace(
+            contract_name.to_string(),
+        ));
+    }
+
+    for segment in segments {
+        if segment.len() > MAX_NAME_SEGMENT_LENGTH {
+            return Err(ContractError::ContractNameTooLong(
+                contract_name.to_string(),
+                MAX_NAME_SEGMENT_LENGTH,
+            ));
+        }
+        if let Some(c) = segment
+            .chars()
+            .find(|c| !(c.is_ascii_lowercase() || c.is_ascii_digit() || *c == '-' || *c == '_'))
+        {
+            return Err(ContractError::InvalidContractNameChar(
+                contract_name.to_string(),
+                c,
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Namespaces follow the same grammar as a single contract name segment.
+pub fn validate_namespace(namespace: &str) -> Result<(), ContractError> {
+    if namespace.contains('/') {
+        return Err(ContractError::InvalidContractNamespace(
+            namespace.to_string(),
+        ));
+    }
+    validate_contract_name(namespace)
+}
+
+/// Returns the namespace of a contract name of the form `namespace/name`.
+pub fn namespace_of(contract_name: &str) -> Option<&str> {
+    contract_name
+        .split_once('/')
+        .map(|(namespace, _)| namespace)
+}
+
+/// Versions must be valid semver, e.g. `1.2.3` or `2.0.0-beta.1`.
+pub fn validate_version(version: &str) -> Result<Version, ContractError> {
+    Version::parse(version)
+        .map_err(|e| ContractError::InvalidVersion(version.to_string(), e.to_string()))
+}
+
+/// Version requirements must be valid semver requirements, e.g. `^1.2` or
+/// `>=1.0.0, <2.0.0`.
+pub fn validate_version_req(version_req: &str) -> Result<VersionReq, ContractError> {
+    VersionReq::parse(version_req)
+        .map_err(|e| ContractError::InvalidVersionReq(version_req.to_string(), e.to_string()))
+}
+
+/// Schema hashes are hex encoded SHA-256 hashes.
+pub fn validate_schema_hash(hash: &str) -> Result<(), ContractError> {
+    if hash.len() != 64 || !hash.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(ContractError::InvalidSchemaHash(hash.to_string()));
+    }
+    Ok(())
+}
+
+/// Registrations must also follow the registry's configured validation
+/// rules.
+pub fn validate_registration(
+    rules: &ValidationRules,
+    msg: &RegisterMsg,
+    version: &Version,
+) -> Result<(), ContractError> {
+    let max_name_length = rules.max_name_length as usize;
+    if msg
+        .contract_name
+        .split('/')
+        .any(|segment| segment.len() > max_name_length)
+    {
+        return Err(ContractError::ContractNameTooLong(
+            msg.contract_name.clone(),
+            max_name_length,
+        ));
+    }
+    if !rules.allow_prerelease && !version.pre.is_empty() {
+        return Err(ContractError::PrereleaseVersion(msg.version.clone()));
+    }
+    if rules.require_checksum
+        && (msg.checksum.len() != 64 || !msg.checksum.chars().all(|c| c.is_ascii_hexdigit()))
+    {
+        return Err(ContractError::InvalidChecksum(msg.checksum.clone()));
+    }
+    Ok(())
+}